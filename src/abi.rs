@@ -0,0 +1,302 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{HexBinary, Uint256};
+use ethabi::{Address, Function, Param, ParamType, StateMutability, Token, Uint};
+
+use crate::address::abi_address;
+use crate::error::ContractError;
+use crate::msg::{AbiArg, AbiType, RemoteFunctionInfo};
+use crate::state::ChainKind;
+
+// Function of the remote connector that this contract knows how to call
+pub struct RemoteFunction {
+    pub name: &'static str,
+    pub inputs: &'static [(&'static str, ParamType)],
+}
+
+// Registry of the remote connector functions driven by the typed execute messages
+pub const REMOTE_FUNCTIONS: &[RemoteFunction] = &[
+    RemoteFunction {
+        name: "set_paloma",
+        inputs: &[],
+    },
+    RemoteFunction {
+        name: "update_withdraw_limit",
+        inputs: &[("new_withdraw_limit", ParamType::Uint(256))],
+    },
+    RemoteFunction {
+        name: "update_pusd",
+        inputs: &[("new_pusd", ParamType::Address)],
+    },
+    RemoteFunction {
+        name: "update_pusd_manager",
+        inputs: &[("new_pusd_manager", ParamType::Address)],
+    },
+    RemoteFunction {
+        name: "update_refund_wallet",
+        inputs: &[("new_refund_wallet", ParamType::Address)],
+    },
+    RemoteFunction {
+        name: "update_gas_fee",
+        inputs: &[("new_gas_fee", ParamType::Uint(256))],
+    },
+    RemoteFunction {
+        name: "update_service_fee_collector",
+        inputs: &[("new_service_fee_collector", ParamType::Address)],
+    },
+    RemoteFunction {
+        name: "update_service_fee",
+        inputs: &[("new_service_fee", ParamType::Uint(256))],
+    },
+];
+
+impl RemoteFunction {
+    pub fn function(&self) -> Function {
+        #[allow(deprecated)]
+        Function {
+            name: self.name.to_string(),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|(name, kind)| Param {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                    internal_type: None,
+                })
+                .collect(),
+            outputs: Vec::new(),
+            constant: None,
+            state_mutability: StateMutability::NonPayable,
+        }
+    }
+
+    pub fn info(&self) -> RemoteFunctionInfo {
+        let function = self.function();
+        RemoteFunctionInfo {
+            name: self.name.to_string(),
+            signature: function.signature(),
+            selector: HexBinary::from(function.short_signature()),
+        }
+    }
+}
+
+pub fn remote_function(name: &str) -> Result<&'static RemoteFunction, ContractError> {
+    REMOTE_FUNCTIONS
+        .iter()
+        .find(|function| function.name == name)
+        .ok_or_else(|| ContractError::UnknownRemoteFunction {
+            name: name.to_string(),
+        })
+}
+
+// Encode a call to a registered remote function
+pub fn encode(name: &str, tokens: &[Token]) -> Result<Vec<u8>, ContractError> {
+    encode_function(&remote_function(name)?.function(), tokens)
+}
+
+pub fn encode_function(function: &Function, tokens: &[Token]) -> Result<Vec<u8>, ContractError> {
+    function
+        .encode_input(tokens)
+        .map_err(|_| ContractError::AbiEncoding {
+            function: function.name.clone(),
+        })
+}
+
+pub fn parse_evm_address(value: &str) -> Result<Address, ContractError> {
+    Address::from_str(value).map_err(|_| ContractError::InvalidEvmAddress {
+        value: value.to_string(),
+    })
+}
+
+pub fn uint256_token(value: Uint256) -> Token {
+    Token::Uint(Uint::from_big_endian(&value.to_be_bytes()))
+}
+
+// Convert a typed JSON argument into its ABI type and token, reading addresses in the
+// format of the target chain
+pub fn arg_token(
+    chain_kind: &ChainKind,
+    arg: &AbiArg,
+) -> Result<(ParamType, Token), ContractError> {
+    Ok(match arg {
+        AbiArg::Address(value) => (
+            ParamType::Address,
+            Token::Address(abi_address(chain_kind, value)?),
+        ),
+        AbiArg::Uint256(value) => (ParamType::Uint(256), uint256_token(*value)),
+        AbiArg::Bool(value) => (ParamType::Bool, Token::Bool(*value)),
+        AbiArg::Bytes(value) => (ParamType::Bytes, Token::Bytes(value.to_vec())),
+        AbiArg::String(value) => (ParamType::String, Token::String(value.clone())),
+        AbiArg::Array { kind, items } => {
+            let kind = param_type(kind);
+            let mut tokens: Vec<Token> = Vec::with_capacity(items.len());
+            for item in items {
+                let (item_kind, token) = arg_token(chain_kind, item)?;
+                if item_kind != kind {
+                    return Err(ContractError::InvalidAbiArgument {
+                        reason: format!("{item_kind} item in a {kind}[] array"),
+                    });
+                }
+                tokens.push(token);
+            }
+            (ParamType::Array(Box::new(kind)), Token::Array(tokens))
+        }
+    })
+}
+
+pub fn param_type(kind: &AbiType) -> ParamType {
+    match kind {
+        AbiType::Address => ParamType::Address,
+        AbiType::Uint256 => ParamType::Uint(256),
+        AbiType::Bool => ParamType::Bool,
+        AbiType::Bytes => ParamType::Bytes,
+        AbiType::String => ParamType::String,
+        AbiType::Array(kind) => ParamType::Array(Box::new(param_type(kind))),
+    }
+}
+
+// Build an ad-hoc function from typed arguments, for functions outside the registry
+pub fn dynamic_function(
+    chain_kind: &ChainKind,
+    name: &str,
+    args: &[AbiArg],
+) -> Result<(Function, Vec<Token>), ContractError> {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(ContractError::InvalidFunctionName {
+            name: name.to_string(),
+        });
+    }
+    // Registered functions go through their typed messages, which record the remote config
+    if REMOTE_FUNCTIONS
+        .iter()
+        .any(|function| function.name == name)
+    {
+        return Err(ContractError::ReservedRemoteFunction {
+            name: name.to_string(),
+        });
+    }
+    let mut inputs: Vec<Param> = Vec::with_capacity(args.len());
+    let mut tokens: Vec<Token> = Vec::with_capacity(args.len());
+    for (index, arg) in args.iter().enumerate() {
+        let (param_type, token) = arg_token(chain_kind, arg)?;
+        inputs.push(Param {
+            name: format!("arg{index}"),
+            kind: param_type,
+            internal_type: None,
+        });
+        tokens.push(token);
+    }
+    #[allow(deprecated)]
+    let function = Function {
+        name: name.to_string(),
+        inputs,
+        outputs: Vec::new(),
+        constant: None,
+        state_mutability: StateMutability::NonPayable,
+    };
+    Ok((function, tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESTINATION: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    fn dynamic_call(name: &str, args: &[AbiArg]) -> Result<HexBinary, ContractError> {
+        let (function, tokens) = dynamic_function(&ChainKind::Evm, name, args)?;
+        Ok(HexBinary::from(encode_function(&function, &tokens)?))
+    }
+
+    fn word(value: u64) -> String {
+        format!("{value:064x}")
+    }
+
+    #[test]
+    fn encodes_an_erc20_transfer() {
+        let payload = dynamic_call(
+            "transfer",
+            &[
+                AbiArg::Address(DESTINATION.to_string()),
+                AbiArg::Uint256(Uint256::from(1_000u32)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            payload.to_hex(),
+            format!(
+                "a9059cbb{:0>64}{}",
+                DESTINATION.trim_start_matches("0x"),
+                word(1_000)
+            )
+        );
+    }
+
+    #[test]
+    fn encodes_a_registered_function() {
+        let function = remote_function("update_withdraw_limit").unwrap().function();
+        assert_eq!(function.signature(), "update_withdraw_limit(uint256)");
+        let payload = encode(
+            "update_withdraw_limit",
+            &[uint256_token(Uint256::from(500u32))],
+        )
+        .unwrap();
+        assert_eq!(payload[..4], function.short_signature());
+        assert_eq!(HexBinary::from(&payload[4..]).to_hex(), word(500));
+    }
+
+    #[test]
+    fn encodes_an_empty_array() {
+        // getAmountsOut(uint256,address[]) of a Uniswap V2 router
+        let payload = dynamic_call(
+            "getAmountsOut",
+            &[
+                AbiArg::Uint256(Uint256::from(1u32)),
+                AbiArg::Array {
+                    kind: AbiType::Address,
+                    items: vec![],
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            payload.to_hex(),
+            format!("d06ca61f{}{}{}", word(1), word(0x40), word(0))
+        );
+    }
+
+    #[test]
+    fn encodes_a_nested_array() {
+        let (function, _) = dynamic_function(
+            &ChainKind::Evm,
+            "f",
+            &[AbiArg::Array {
+                kind: AbiType::Array(Box::new(AbiType::Bool)),
+                items: vec![AbiArg::Array {
+                    kind: AbiType::Bool,
+                    items: vec![AbiArg::Bool(true)],
+                }],
+            }],
+        )
+        .unwrap();
+        assert_eq!(function.signature(), "f(bool[][])");
+    }
+
+    #[test]
+    fn rejects_items_of_another_kind() {
+        let err = dynamic_call(
+            "f",
+            &[AbiArg::Array {
+                kind: AbiType::Address,
+                items: vec![AbiArg::Bool(true)],
+            }],
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidAbiArgument { .. }));
+    }
+}
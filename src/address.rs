@@ -0,0 +1,181 @@
+use ethabi::Address;
+use sha2::{Digest, Sha256};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::abi::parse_evm_address;
+use crate::error::ContractError;
+use crate::state::ChainKind;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Version byte of Tron mainnet account addresses
+const TRON_PREFIX: u8 = 0x41;
+
+fn invalid(kind: &ChainKind, value: &str) -> ContractError {
+    ContractError::InvalidAddress {
+        kind: kind.as_str().to_string(),
+        value: value.to_string(),
+    }
+}
+
+// Check an address of a remote chain against the format of its kind
+pub fn validate(kind: &ChainKind, value: &str) -> Result<(), ContractError> {
+    let valid = match kind {
+        ChainKind::Evm => is_evm_address(value),
+        ChainKind::Tron => decode_tron(value).is_some(),
+        ChainKind::Solana => base58_decode(value).is_some_and(|bytes| bytes.len() == 32),
+        ChainKind::Cosmos { prefix } => {
+            bech32::decode(value).is_ok_and(|(hrp, _)| hrp.as_str().eq_ignore_ascii_case(prefix))
+        }
+    };
+    if !valid {
+        return Err(invalid(kind, value));
+    }
+    Ok(())
+}
+
+// Form used to match an address in the compliance lists. Hex and bech32 addresses are
+// case-insensitive, base58 ones are not.
+pub fn canonical(value: &str) -> String {
+    let is_hex = value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if is_hex || bech32::decode(value).is_ok() {
+        value.to_ascii_lowercase()
+    } else {
+        value.to_string()
+    }
+}
+
+// Address in the 20-byte ABI form expected by the remote connector of the chain
+pub fn abi_address(kind: &ChainKind, value: &str) -> Result<Address, ContractError> {
+    match kind {
+        ChainKind::Evm if is_evm_address(value) => parse_evm_address(value),
+        ChainKind::Tron => decode_tron(value)
+            .map(Address::from)
+            .ok_or_else(|| invalid(kind, value)),
+        _ => Err(invalid(kind, value)),
+    }
+}
+
+fn base58_decode(value: &str) -> Option<Vec<u8>> {
+    // Little-endian bytes of the number, multiplied by 58 per digit
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in value.chars() {
+        let mut carry = BASE58_ALPHABET.find(c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' stands for a leading zero byte
+    let zeros = value.chars().take_while(|&c| c == '1').count();
+    bytes.extend(std::iter::repeat_n(0, zeros));
+    bytes.reverse();
+    Some(bytes)
+}
+
+// 20-byte account of a base58check Tron address: 0x41 prefix, account, 4-byte checksum
+pub fn decode_tron(value: &str) -> Option<[u8; 20]> {
+    let bytes = base58_decode(value)?;
+    if bytes.len() != 25 || bytes[0] != TRON_PREFIX {
+        return None;
+    }
+    let (payload, checksum) = bytes.split_at(21);
+    if Sha256::digest(Sha256::digest(payload))[..4] != *checksum {
+        return None;
+    }
+    payload[1..].try_into().ok()
+}
+
+// 0x-prefixed 20-byte hex. Mixed-case addresses must carry a valid EIP-55 checksum.
+pub fn is_evm_address(value: &str) -> bool {
+    let hex = match value.strip_prefix("0x") {
+        Some(hex) if hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()) => hex,
+        _ => return false,
+    };
+    let is_lower = !hex.chars().any(|c| c.is_ascii_uppercase());
+    let is_upper = !hex.chars().any(|c| c.is_ascii_lowercase());
+    is_lower || is_upper || to_checksum(hex) == hex
+}
+
+// EIP-55 casing of a 40-character hex address
+fn to_checksum(hex: &str) -> String {
+    let lower = hex.to_ascii_lowercase();
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(lower.as_bytes());
+    keccak.finalize(&mut hash);
+    lower
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let nibble = (hash[index / 2] >> (if index % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRON_USDT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    #[test]
+    fn decodes_tron_base58check() {
+        let account = decode_tron(TRON_USDT).unwrap();
+        assert_eq!(
+            Address::from(account),
+            parse_evm_address("0xa614f803b6fd780986a42c78ec9c7f77e6ded13c").unwrap()
+        );
+        assert_eq!(
+            abi_address(&ChainKind::Tron, TRON_USDT).unwrap(),
+            Address::from(account)
+        );
+    }
+
+    #[test]
+    fn rejects_tron_bad_checksum() {
+        let bad = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u";
+        assert_eq!(decode_tron(bad), None);
+        assert!(validate(&ChainKind::Tron, bad).is_err());
+        assert!(abi_address(&ChainKind::Tron, bad).is_err());
+    }
+
+    #[test]
+    fn checks_eip55_casing() {
+        assert!(is_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(is_evm_address("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+        assert!(is_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+        assert!(is_evm_address("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"));
+        assert!(!is_evm_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+        ));
+        assert!(!is_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+    }
+
+    #[test]
+    fn decodes_solana_leading_ones() {
+        assert_eq!(
+            base58_decode("11111111111111111111111111111111"),
+            Some(vec![0; 32])
+        );
+        assert!(validate(&ChainKind::Solana, "11111111111111111111111111111111").is_ok());
+        assert!(validate(
+            &ChainKind::Solana,
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        )
+        .is_ok());
+        assert!(validate(&ChainKind::Solana, "1111111111111111111111111111111").is_err());
+    }
+}
@@ -1,21 +1,85 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, Uint128,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 
+use crate::abi;
+use crate::address;
 use crate::error::ContractError;
-use crate::msg::{ChainSettingInfo, ExecuteMsg, InstantiateMsg, MigrateMsg, PalomaMsg, QueryMsg};
-use crate::state::{State, CHAIN_SETTINGS, STATE};
+use crate::msg::{
+    AssetInfo, ChainRegistration, ChainSettingInfo, DestinationStatusResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, OwnershipResponse, PalomaMsg, PauseStatusResponse, QueryMsg,
+    QuoteResponse, RateLimitResponse, RemoteFunctionInfo, SudoMsg, TransferFilter,
+    UserQuotaResponse,
+};
+use crate::skyway;
+use crate::state::{
+    default_pusd_denom, transfers, Asset, ChainSetting, RateLimitUsage, Role, State, Transfer,
+    TransferOrigin, TransferStatus, UserUsage, WithdrawActionKind, ALLOWED_DESTINATIONS,
+    ALLOWLIST_CHAINS, ASSETS, CHAIN_SETTINGS, DEFAULT_USER_QUOTAS, DENIED_DESTINATIONS,
+    DENIED_SENDERS, FEE_BALANCES, FEE_SCHEDULES, JOB_RESULTS, LEGACY_CHAIN_SETTINGS, LEGACY_STATE,
+    PAUSED, PAUSED_CHAINS, PENDING_OWNER, RATE_LIMITS, RATE_LIMIT_USAGE, ROLES, SECONDS_PER_DAY,
+    STATE, TRANSFER_COUNT, USED_NONCES, USER_QUOTAS, USER_USAGE, WITHDRAW_ACTIONS,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:pusd-connector-cw";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// reply id of the Skyway send_tx submessage
+const SEND_TX_REPLY_ID: u64 = 1;
+
+// asset registered at instantiation, and under which legacy pusd settings are migrated
+const DEFAULT_ASSET_ID: &str = "pusd";
+const PUSD_DECIMALS: u8 = 6;
+
+// pagination limits for list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // Move the single pusd configuration into the asset registry
+    if let Ok(legacy) = LEGACY_STATE.load(deps.storage) {
+        let denom = if legacy.pusd_denom.is_empty() {
+            default_pusd_denom(&legacy.pusd_manager)
+        } else {
+            legacy.pusd_denom
+        };
+        ASSETS.save(
+            deps.storage,
+            DEFAULT_ASSET_ID.to_string(),
+            &Asset {
+                denom,
+                manager: legacy.pusd_manager,
+                decimals: PUSD_DECIMALS,
+            },
+        )?;
+        STATE.save(
+            deps.storage,
+            &State {
+                owner: legacy.owner,
+                default_asset: DEFAULT_ASSET_ID.to_string(),
+            },
+        )?;
+    }
+    let default_asset = STATE.load(deps.storage)?.default_asset;
+    let legacy_chains = LEGACY_CHAIN_SETTINGS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, ChainSetting)>>>()?;
+    for (chain_id, chain_setting) in legacy_chains {
+        CHAIN_SETTINGS.save(
+            deps.storage,
+            (default_asset.clone(), chain_id.clone()),
+            &chain_setting,
+        )?;
+        LEGACY_CHAIN_SETTINGS.remove(deps.storage, chain_id);
+    }
     Ok(Response::default())
 }
 
@@ -26,606 +90,2065 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    let pusd_denom = msg
+        .pusd_denom
+        .unwrap_or_else(|| default_pusd_denom(&msg.pusd_manager));
+    skyway::validate_denom(&pusd_denom)?;
     let state = State {
         owner: info.sender.clone(),
-        pusd_manager: msg.pusd_manager.clone(),
+        default_asset: DEFAULT_ASSET_ID.to_string(),
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     STATE.save(deps.storage, &state)?;
+    ASSETS.save(
+        deps.storage,
+        DEFAULT_ASSET_ID.to_string(),
+        &Asset {
+            denom: pusd_denom.clone(),
+            manager: msg.pusd_manager.clone(),
+            decimals: PUSD_DECIMALS,
+        },
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender)
-        .add_attribute("pusd_manager", msg.pusd_manager))
+        .add_attribute("asset_id", DEFAULT_ASSET_ID)
+        .add_attribute("pusd_manager", msg.pusd_manager)
+        .add_attribute("pusd_denom", pusd_denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<PalomaMsg>, ContractError> {
+    if let Some(role) = msg.required_role() {
+        ensure_role(deps.storage, &info.sender, role)?;
+    }
     match msg {
         ExecuteMsg::RegisterChain {
             chain_id,
+            asset_id,
             chain_setting,
-        } => execute::register_chain(deps, info, chain_id, chain_setting),
+        } => execute::register_chain(deps, asset_id, chain_id, chain_setting),
+        ExecuteMsg::DeregisterChain { chain_id, asset_id } => {
+            execute::deregister_chain(deps, asset_id, chain_id)
+        }
+        ExecuteMsg::SetChainEnabled {
+            chain_id,
+            asset_id,
+            enabled,
+        } => execute::set_chain_enabled(deps, asset_id, chain_id, enabled),
         ExecuteMsg::SendPusd {
             chain_id,
+            asset_id,
             to,
             amount,
             nonce,
-        } => execute::send_pusd(deps, info, chain_id, to, amount, nonce),
+        } => execute::send_pusd(deps, env, info, asset_id, chain_id, to, amount, nonce),
+        ExecuteMsg::Bridge {
+            chain_id,
+            asset_id,
+            to,
+        } => execute::bridge(deps, env, info, asset_id, chain_id, to),
         ExecuteMsg::WithdrawPusd {
             chain_id,
+            asset_id,
             recipient,
             amount,
-        } => execute::withdraw_pusd(deps, info, chain_id, recipient, amount),
-        ExecuteMsg::CancelTx { transaction_id } => execute::cancel_tx(deps, info, transaction_id),
-        ExecuteMsg::ChangeConfig {
-            owner,
-            pusd_manager,
-        } => execute::change_config(deps, info, owner, pusd_manager),
-        ExecuteMsg::SetPaloma { chain_id } => execute::set_paloma(deps, info, chain_id),
+        } => execute::withdraw_pusd(deps, info, asset_id, chain_id, recipient, amount),
+        ExecuteMsg::ReWithdrawPusd { nonce, asset_id } => execute::manage_withdraw(
+            deps,
+            env,
+            info,
+            asset_id,
+            nonce,
+            WithdrawActionKind::ReWithdraw,
+        ),
+        ExecuteMsg::CancelWithdrawPusd { nonce, asset_id } => execute::manage_withdraw(
+            deps,
+            env,
+            info,
+            asset_id,
+            nonce,
+            WithdrawActionKind::CancelWithdraw,
+        ),
+        ExecuteMsg::CancelTx {
+            transaction_id,
+            transfer_id,
+        } => execute::cancel_tx(deps, env, transaction_id, transfer_id),
+        ExecuteMsg::CancelTransfer {
+            chain_id,
+            asset_id,
+            nonce,
+        } => execute::cancel_transfer(deps, env, chain_id, asset_id, nonce),
+        ExecuteMsg::RegisterAsset {
+            asset_id,
+            manager,
+            denom,
+            decimals,
+        } => execute::register_asset(deps, asset_id, manager, denom, decimals),
+        ExecuteMsg::ChangeConfig { default_asset } => execute::change_config(deps, default_asset),
+        ExecuteMsg::ProposeOwner {
+            new_owner,
+            expires_at,
+        } => execute::propose_owner(deps, env, info, new_owner, expires_at),
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipProposal {} => execute::cancel_ownership_proposal(deps, info),
+        ExecuteMsg::SetPaloma { chain_id, asset_id } => {
+            execute::set_paloma(deps, env, asset_id, chain_id)
+        }
         ExecuteMsg::UpdateWithdrawLimit {
             chain_id,
+            asset_id,
             new_withdraw_limit,
-        } => execute::update_withdraw_limit(deps, info, chain_id, new_withdraw_limit),
-        ExecuteMsg::UpdatePusd { chain_id, new_pusd } => {
-            execute::update_pusd(deps, info, chain_id, new_pusd)
-        }
+        } => execute::update_withdraw_limit(deps, env, asset_id, chain_id, new_withdraw_limit),
+        ExecuteMsg::UpdatePusd {
+            chain_id,
+            asset_id,
+            new_pusd,
+        } => execute::update_pusd(deps, env, asset_id, chain_id, new_pusd),
         ExecuteMsg::UpdatePusdManager {
             chain_id,
+            asset_id,
             new_pusd_manager,
-        } => execute::update_pusd_manager(deps, info, chain_id, new_pusd_manager),
+        } => execute::update_pusd_manager(deps, env, asset_id, chain_id, new_pusd_manager),
         ExecuteMsg::UpdateRefundWallet {
             chain_id,
+            asset_id,
             new_refund_wallet,
-        } => execute::update_refund_wallet(deps, info, chain_id, new_refund_wallet),
+        } => execute::update_refund_wallet(deps, env, asset_id, chain_id, new_refund_wallet),
         ExecuteMsg::UpdateGasFee {
             chain_id,
+            asset_id,
             new_gas_fee,
-        } => execute::update_gas_fee(deps, info, chain_id, new_gas_fee),
+        } => execute::update_gas_fee(deps, env, asset_id, chain_id, new_gas_fee),
         ExecuteMsg::UpdateServiceFeeCollector {
+            chain_id,
+            asset_id,
+            new_service_fee_collector,
+        } => execute::update_service_fee_collector(
+            deps,
+            env,
+            asset_id,
             chain_id,
             new_service_fee_collector,
-        } => execute::update_service_fee_collector(deps, info, chain_id, new_service_fee_collector),
+        ),
         ExecuteMsg::UpdateServiceFee {
             chain_id,
+            asset_id,
             new_service_fee,
-        } => execute::update_service_fee(deps, info, chain_id, new_service_fee),
+        } => execute::update_service_fee(deps, env, asset_id, chain_id, new_service_fee),
+        ExecuteMsg::GrantRole { role, address } => execute::grant_role(deps, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute::revoke_role(deps, role, address),
+        ExecuteMsg::RenounceRole { role } => execute::renounce_role(deps, info, role),
+        ExecuteMsg::Pause { chain_id } => execute::set_paused(deps, chain_id, true),
+        ExecuteMsg::Unpause { chain_id } => execute::set_paused(deps, chain_id, false),
+        ExecuteMsg::ExecuteRemote {
+            chain_id,
+            asset_id,
+            function_name,
+            args,
+        } => execute::execute_remote(deps, asset_id, chain_id, function_name, args),
+        ExecuteMsg::SetFeeSchedule {
+            chain_id,
+            asset_id,
+            fee_schedule,
+        } => execute::set_fee_schedule(deps, asset_id, chain_id, fee_schedule),
+        ExecuteMsg::SetRateLimit {
+            chain_id,
+            asset_id,
+            rate_limit,
+        } => execute::set_rate_limit(deps, asset_id, chain_id, rate_limit),
+        ExecuteMsg::SetDefaultUserQuota { asset_id, quota } => {
+            execute::set_default_user_quota(deps, asset_id, quota)
+        }
+        ExecuteMsg::SetUserQuota {
+            address,
+            asset_id,
+            quota,
+        } => execute::set_user_quota(deps, asset_id, address, quota),
+        ExecuteMsg::SetSenderDenied { address, denied } => {
+            execute::set_sender_denied(deps, address, denied)
+        }
+        ExecuteMsg::SetDestinationDenied {
+            chain_id,
+            address,
+            denied,
+        } => execute::set_destination_denied(deps, chain_id, address, denied),
+        ExecuteMsg::SetDestinationAllowed {
+            chain_id,
+            address,
+            allowed,
+        } => execute::set_destination_allowed(deps, chain_id, address, allowed),
+        ExecuteMsg::SetAllowlistMode { chain_id, enabled } => {
+            execute::set_allowlist_mode(deps, chain_id, enabled)
+        }
+        ExecuteMsg::ClaimFees {
+            asset_id,
+            recipient,
+        } => execute::claim_fees(deps, info, asset_id, recipient),
     }
 }
 
-pub mod execute {
-    use std::collections::BTreeMap;
+// Owner passes every role check
+pub fn has_role(storage: &dyn Storage, address: &Addr, role: Role) -> StdResult<bool> {
+    if STATE.load(storage)?.owner == *address {
+        return Ok(true);
+    }
+    Ok(ROLES.has(storage, (role, address.clone())))
+}
+
+// The denom of an asset can only change once nothing is held or in flight under the old one
+pub fn ensure_denom_unused(
+    storage: &dyn Storage,
+    asset_id: &str,
+    denom: &str,
+) -> Result<(), ContractError> {
+    let fees = FEE_BALANCES
+        .may_load(storage, denom.to_string())?
+        .unwrap_or_default();
+    if !fees.is_zero() {
+        return Err(ContractError::AssetInUse {
+            asset_id: asset_id.to_string(),
+            reason: format!("{fees}{denom} of unclaimed fees"),
+        });
+    }
+    let pending = transfers()
+        .idx
+        .asset_status
+        .prefix((
+            asset_id.to_string(),
+            TransferStatus::Pending.as_str().to_string(),
+        ))
+        .keys_raw(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if pending {
+        return Err(ContractError::AssetInUse {
+            asset_id: asset_id.to_string(),
+            reason: "pending transfers".to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Resolve an optional asset id, falling back to the default asset
+pub fn load_asset(
+    storage: &dyn Storage,
+    asset_id: Option<String>,
+) -> Result<(String, Asset), ContractError> {
+    let asset_id = match asset_id {
+        Some(asset_id) => asset_id,
+        None => STATE.load(storage)?.default_asset,
+    };
+    let asset =
+        ASSETS
+            .may_load(storage, asset_id.clone())?
+            .ok_or_else(|| ContractError::UnknownAsset {
+                asset_id: asset_id.clone(),
+            })?;
+    Ok((asset_id, asset))
+}
+
+pub fn load_chain_setting(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+) -> Result<ChainSetting, ContractError> {
+    CHAIN_SETTINGS
+        .may_load(storage, (asset_id.to_string(), chain_id.to_string()))?
+        .ok_or_else(|| ContractError::UnknownChain {
+            chain_id: chain_id.to_string(),
+        })
+}
+
+// Load a chain that outbound flows may currently target
+pub fn load_active_chain(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+) -> Result<ChainSetting, ContractError> {
+    let chain_setting = load_chain_setting(storage, asset_id, chain_id)?;
+    if !chain_setting.enabled {
+        return Err(ContractError::ChainDisabled {
+            chain_id: chain_id.to_string(),
+        });
+    }
+    Ok(chain_setting)
+}
+
+// Check an address on a chain against the format of its chain kind
+pub fn validate_remote_address(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    value: &str,
+) -> Result<(), ContractError> {
+    let chain_setting = load_chain_setting(storage, asset_id, chain_id)?;
+    address::validate(&chain_setting.chain_kind, value)
+}
+
+// Address on a chain in the ABI form its remote connector expects
+pub fn remote_abi_address(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    value: &str,
+) -> Result<ethabi::Address, ContractError> {
+    let chain_setting = load_chain_setting(storage, asset_id, chain_id)?;
+    address::abi_address(&chain_setting.chain_kind, value)
+}
+
+pub fn load_transfer(storage: &dyn Storage, id: u64) -> Result<Transfer, ContractError> {
+    transfers()
+        .may_load(storage, id)?
+        .ok_or(ContractError::TransferNotFound { id })
+}
+
+// Pay back the sender of a Bridge transfer whose coins Skyway returned to this contract.
+// Operator transfers are funded by the contract, so their coins simply stay with it.
+pub fn refund_bridge_transfer(
+    storage: &mut dyn Storage,
+    transfer: &mut Transfer,
+) -> StdResult<Option<BankMsg>> {
+    let returned = matches!(
+        transfer.status,
+        TransferStatus::Cancelled | TransferStatus::Refunded
+    );
+    if transfer.origin != TransferOrigin::Bridge || !returned || transfer.refunded.is_some() {
+        return Ok(None);
+    }
+    // The fee goes back too, as far as it has not been claimed yet
+    let fee_balance = FEE_BALANCES
+        .may_load(storage, transfer.denom.clone())?
+        .unwrap_or_default();
+    let fee = transfer.fee.min(fee_balance);
+    if fee == fee_balance {
+        FEE_BALANCES.remove(storage, transfer.denom.clone());
+    } else {
+        FEE_BALANCES.save(storage, transfer.denom.clone(), &(fee_balance - fee))?;
+    }
+    let amount = transfer.amount.checked_add(fee)?;
+    transfer.refunded = Some(amount);
+    Ok(Some(BankMsg::Send {
+        to_address: transfer.sender.to_string(),
+        amount: vec![Coin {
+            denom: transfer.denom.clone(),
+            amount,
+        }],
+    }))
+}
+
+// Move a transfer to `status`, with the refund owed to its sender if any
+pub fn update_transfer_status(
+    storage: &mut dyn Storage,
+    env: &Env,
+    id: u64,
+    status: TransferStatus,
+) -> Result<(Transfer, Option<BankMsg>), ContractError> {
+    let mut transfer = load_transfer(storage, id)?;
+    if !transfer.status.can_transition_to(status) {
+        return Err(ContractError::InvalidTransferStatus {
+            id,
+            status: transfer.status.as_str().to_string(),
+        });
+    }
+    transfer.status = status;
+    transfer.updated_at = env.block.time;
+    let refund = refund_bridge_transfer(storage, &mut transfer)?;
+    transfers().save(storage, id, &transfer)?;
+    Ok((transfer, refund))
+}
+
+pub fn ensure_not_paused(storage: &dyn Storage, chain_id: &str) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or_default()
+        || PAUSED_CHAINS.has(storage, chain_id.to_string())
+    {
+        return Err(ContractError::Paused {
+            chain_id: chain_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Global pause flag alone, for flows not bound to a chain
+pub fn ensure_not_paused_globally(storage: &dyn Storage) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::PausedGlobally {});
+    }
+    Ok(())
+}
+
+// Fee charged by this contract on a Bridge send of `amount`
+pub fn bridge_fee(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    Ok(
+        match FEE_SCHEDULES.may_load(storage, (asset_id.to_string(), chain_id.to_string()))? {
+            Some(fee_schedule) => fee_schedule.fee(amount)?,
+            None => Uint128::zero(),
+        },
+    )
+}
+
+// Rate limit usage of a chain, rolled forward to `now`
+pub fn load_rate_limit_usage(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    now: u64,
+    window_seconds: u64,
+) -> StdResult<RateLimitUsage> {
+    let mut usage = RATE_LIMIT_USAGE
+        .may_load(storage, (asset_id.to_string(), chain_id.to_string()))?
+        .unwrap_or_default();
+    usage.roll(now, window_seconds);
+    Ok(usage)
+}
+
+pub fn rate_limit_status(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    now: u64,
+) -> StdResult<RateLimitResponse> {
+    let rate_limit = RATE_LIMITS.may_load(storage, (asset_id.to_string(), chain_id.to_string()))?;
+    let used = match &rate_limit {
+        Some(rate_limit) => {
+            load_rate_limit_usage(storage, asset_id, chain_id, now, rate_limit.window_seconds)?
+                .used(now, rate_limit.window_seconds)
+        }
+        None => Uint128::zero(),
+    };
+    Ok(RateLimitResponse {
+        remaining: rate_limit
+            .as_ref()
+            .map(|rate_limit| rate_limit.max_amount.saturating_sub(used)),
+        rate_limit,
+        used,
+    })
+}
 
-    use cosmwasm_std::{Addr, Coin, CosmosMsg, Uint128, Uint256, WasmMsg};
-    use ethabi::{Address, Contract, Function, Param, ParamType, StateMutability, Token, Uint};
+pub fn ensure_within_rate_limit(
+    storage: &dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    now: u64,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let status = rate_limit_status(storage, asset_id, chain_id, now)?;
+    if let Some(max) = status
+        .rate_limit
+        .and_then(|rate_limit| rate_limit.max_per_transfer)
+    {
+        if amount > max {
+            return Err(ContractError::TransferTooLarge {
+                chain_id: chain_id.to_string(),
+                max,
+            });
+        }
+    }
+    if let Some(remaining) = status.remaining {
+        if amount > remaining {
+            return Err(ContractError::RateLimitExceeded {
+                chain_id: chain_id.to_string(),
+                remaining,
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn record_rate_limit_usage(
+    storage: &mut dyn Storage,
+    asset_id: &str,
+    chain_id: &str,
+    now: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let rate_limit =
+        match RATE_LIMITS.may_load(storage, (asset_id.to_string(), chain_id.to_string()))? {
+            Some(rate_limit) => rate_limit,
+            None => return Ok(()),
+        };
+    let mut usage =
+        load_rate_limit_usage(storage, asset_id, chain_id, now, rate_limit.window_seconds)?;
+    usage.current = usage.current.saturating_add(amount);
+    RATE_LIMIT_USAGE.save(
+        storage,
+        (asset_id.to_string(), chain_id.to_string()),
+        &usage,
+    )
+}
+
+pub fn user_quota_status(
+    storage: &dyn Storage,
+    asset_id: &str,
+    address: &Addr,
+    now: u64,
+) -> StdResult<UserQuotaResponse> {
+    let key = (asset_id.to_string(), address.clone());
+    let quota = match USER_QUOTAS.may_load(storage, key.clone())? {
+        Some(quota) => Some(quota),
+        None => DEFAULT_USER_QUOTAS.may_load(storage, asset_id.to_string())?,
+    };
+    let day = now / SECONDS_PER_DAY;
+    let used = USER_USAGE
+        .may_load(storage, key)?
+        .filter(|usage| usage.day == day)
+        .map(|usage| usage.used)
+        .unwrap_or_default();
+    Ok(UserQuotaResponse {
+        quota,
+        day,
+        used,
+        remaining: quota.map(|quota| quota.saturating_sub(used)),
+    })
+}
+
+pub fn ensure_within_user_quota(
+    storage: &dyn Storage,
+    asset_id: &str,
+    address: &Addr,
+    now: u64,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if let Some(remaining) = user_quota_status(storage, asset_id, address, now)?.remaining {
+        if amount > remaining {
+            return Err(ContractError::UserQuotaExceeded {
+                address: address.to_string(),
+                remaining,
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn record_user_usage(
+    storage: &mut dyn Storage,
+    asset_id: &str,
+    address: &Addr,
+    now: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let status = user_quota_status(storage, asset_id, address, now)?;
+    USER_USAGE.save(
+        storage,
+        (asset_id.to_string(), address.clone()),
+        &UserUsage {
+            day: status.day,
+            used: status.used.saturating_add(amount),
+        },
+    )
+}
+
+pub fn ensure_sender_allowed(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    if DENIED_SENDERS.has(storage, sender.clone()) {
+        return Err(ContractError::SenderDenied {
+            address: sender.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn destination_status(
+    storage: &dyn Storage,
+    chain_id: &str,
+    address: &str,
+) -> DestinationStatusResponse {
+    let key = (chain_id.to_string(), address::canonical(address));
+    let denied = DENIED_DESTINATIONS.has(storage, key.clone());
+    let allowlist_mode = ALLOWLIST_CHAINS.has(storage, chain_id.to_string());
+    let allowlisted = ALLOWED_DESTINATIONS.has(storage, key);
+    DestinationStatusResponse {
+        denied,
+        allowlist_mode,
+        allowlisted,
+        allowed: !denied && (!allowlist_mode || allowlisted),
+    }
+}
+
+pub fn ensure_destination_allowed(
+    storage: &dyn Storage,
+    chain_id: &str,
+    address: &str,
+) -> Result<(), ContractError> {
+    let status = destination_status(storage, chain_id, address);
+    if status.denied {
+        return Err(ContractError::DestinationDenied {
+            chain_id: chain_id.to_string(),
+            address: address.to_string(),
+        });
+    }
+    if !status.allowed {
+        return Err(ContractError::DestinationNotAllowed {
+            chain_id: chain_id.to_string(),
+            address: address.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Checks on the sender and destination of an outbound transfer, each skipped when unknown
+pub fn ensure_parties_allowed(
+    storage: &dyn Storage,
+    now: u64,
+    asset_id: &str,
+    chain_id: &str,
+    amount: Uint128,
+    sender: Option<&Addr>,
+    to: Option<&str>,
+) -> Result<(), ContractError> {
+    if let Some(sender) = sender {
+        ensure_within_user_quota(storage, asset_id, sender, now, amount)?;
+    }
+    if let Some(to) = to {
+        validate_remote_address(storage, asset_id, chain_id, to)?;
+    }
+    if let Some(sender) = sender {
+        ensure_sender_allowed(storage, sender)?;
+    }
+    if let Some(to) = to {
+        ensure_destination_allowed(storage, chain_id, to)?;
+    }
+    Ok(())
+}
+
+// Checks an outbound transfer must pass before its send_tx is issued
+pub fn ensure_can_transfer(
+    storage: &dyn Storage,
+    now: u64,
+    asset_id: &str,
+    asset: &Asset,
+    chain_id: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    ensure_not_paused(storage, chain_id)?;
+    load_active_chain(storage, asset_id, chain_id)?;
+    skyway::validate_amount(amount, asset.decimals)?;
+    ensure_within_rate_limit(storage, asset_id, chain_id, now, amount)
+}
+
+pub fn quote_send(
+    storage: &dyn Storage,
+    now: u64,
+    asset_id: Option<String>,
+    chain_id: String,
+    amount: Uint128,
+    sender: Option<Addr>,
+    to: Option<String>,
+) -> Result<QuoteResponse, ContractError> {
+    let (asset_id, asset) = load_asset(storage, asset_id)?;
+    let service_fee = bridge_fee(storage, &asset_id, &chain_id, amount)?;
+    let net_amount = amount.saturating_sub(service_fee);
+    let rejection = if !service_fee.is_zero() && service_fee >= amount {
+        Some(ContractError::FeeExceedsAmount {
+            amount,
+            fee: service_fee,
+        })
+    } else {
+        ensure_can_transfer(storage, now, &asset_id, &asset, &chain_id, net_amount)
+            .and_then(|()| {
+                ensure_parties_allowed(
+                    storage,
+                    now,
+                    &asset_id,
+                    &chain_id,
+                    net_amount,
+                    sender.as_ref(),
+                    to.as_deref(),
+                )
+            })
+            .err()
+    };
+    let remote = CHAIN_SETTINGS
+        .may_load(storage, (asset_id.clone(), chain_id))?
+        .map(|chain_setting| chain_setting.remote)
+        .unwrap_or_default();
+    Ok(QuoteResponse {
+        asset_id,
+        denom: asset.denom,
+        gross_amount: amount,
+        service_fee,
+        net_amount,
+        remote_gas_fee: remote.gas_fee.map(|fee| fee.value),
+        remote_service_fee: remote.service_fee.map(|fee| fee.value),
+        rejection: rejection.map(|error| error.to_string()),
+    })
+}
+
+// Amount of the single `denom` coin sent with the message
+pub fn paid_amount(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds {
+            denom: denom.to_string(),
+        }),
+    }
+}
+
+pub fn ensure_role(storage: &dyn Storage, address: &Addr, role: Role) -> Result<(), ContractError> {
+    if !has_role(storage, address, role)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub mod execute {
+    use cosmwasm_std::{BankMsg, CosmosMsg, SubMsg, Timestamp, Uint256, WasmMsg};
+    use ethabi::Token;
 
     use super::*;
     use crate::{
-        msg::{CancelTx, ExecuteJob, ExternalExecuteMsg, PalomaMsg, SendTx},
-        state::{ChainSetting, CHAIN_SETTINGS},
+        abi,
+        msg::{AbiArg, ExecuteJob, ExternalExecuteMsg, PalomaMsg},
+        state::{
+            ChainSetting, FeeSchedule, PendingOwner, RateLimit, Recorded, RemoteConfig,
+            WithdrawAction, CHAIN_SETTINGS, MAX_FEE_BPS, SKYWAY_TXS,
+        },
     };
-    use std::str::FromStr;
+
+    // Scheduler job calling `payload` on the remote connector of a chain
+    fn remote_job(
+        storage: &dyn Storage,
+        asset_id: &str,
+        chain_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<CosmosMsg<PalomaMsg>, ContractError> {
+        Ok(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
+            execute_job: ExecuteJob {
+                job_id: load_active_chain(storage, asset_id, chain_id)?.job_id,
+                payload: Binary::new(payload),
+            },
+        }))
+    }
+
+    // Remember the configuration pushed to the remote connector of a chain
+    fn record_remote(
+        storage: &mut dyn Storage,
+        asset_id: &str,
+        chain_id: &str,
+        record: impl FnOnce(&mut RemoteConfig),
+    ) -> Result<(), ContractError> {
+        let mut chain_setting = load_chain_setting(storage, asset_id, chain_id)?;
+        record(&mut chain_setting.remote);
+        CHAIN_SETTINGS.save(
+            storage,
+            (asset_id.to_string(), chain_id.to_string()),
+            &chain_setting,
+        )?;
+        Ok(())
+    }
 
     pub fn register_chain(
         deps: DepsMut,
-        info: MessageInfo,
+        asset_id: Option<String>,
         chain_id: String,
-        chain_setting: ChainSetting,
+        registration: ChainRegistration,
     ) -> Result<Response<PalomaMsg>, ContractError> {
-        let state = STATE.load(deps.storage)?;
-        if info.sender != state.owner {
-            return Err(ContractError::Unauthorized {});
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let key = (asset_id.clone(), chain_id.clone());
+        let mut chain_setting = ChainSetting {
+            job_id: registration.job_id,
+            chain_kind: registration.chain_kind,
+            enabled: true,
+            remote: RemoteConfig::default(),
+        };
+        // Re-registering a chain keeps what was already pushed to its connector
+        if let Some(existing) = CHAIN_SETTINGS.may_load(deps.storage, key.clone())? {
+            chain_setting.enabled = existing.enabled;
+            chain_setting.remote = existing.remote;
         }
-        CHAIN_SETTINGS.save(deps.storage, chain_id.clone(), &chain_setting)?;
+        CHAIN_SETTINGS.save(deps.storage, key, &chain_setting)?;
         Ok(Response::new()
             .add_attribute("action", "register_chain")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("chain_id", chain_id))
+    }
+
+    pub fn deregister_chain(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        chain_id: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+        let key = (asset_id.clone(), chain_id.clone());
+        CHAIN_SETTINGS.remove(deps.storage, key.clone());
+        // A later registration of the chain starts without the old limits, usage and fees
+        RATE_LIMITS.remove(deps.storage, key.clone());
+        RATE_LIMIT_USAGE.remove(deps.storage, key.clone());
+        FEE_SCHEDULES.remove(deps.storage, key);
+        Ok(Response::new()
+            .add_attribute("action", "deregister_chain")
+            .add_attribute("asset_id", asset_id)
             .add_attribute("chain_id", chain_id))
     }
 
+    pub fn set_chain_enabled(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        chain_id: String,
+        enabled: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let mut chain_setting = load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+        chain_setting.enabled = enabled;
+        CHAIN_SETTINGS.save(
+            deps.storage,
+            (asset_id.clone(), chain_id.clone()),
+            &chain_setting,
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "set_chain_enabled")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    // Record a transfer and issue its Skyway send_tx
+    #[allow(clippy::too_many_arguments)]
+    fn issue_transfer(
+        deps: DepsMut,
+        env: &Env,
+        origin: TransferOrigin,
+        sender: Addr,
+        (asset_id, asset): (String, Asset),
+        chain_id: String,
+        to: String,
+        amount: Uint128,
+        fee: Uint128,
+        nonce: Option<Uint128>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let now = env.block.time.seconds();
+        ensure_can_transfer(deps.storage, now, &asset_id, &asset, &chain_id, amount)?;
+        ensure_parties_allowed(
+            deps.storage,
+            now,
+            &asset_id,
+            &chain_id,
+            amount,
+            Some(&sender),
+            Some(&to),
+        )?;
+        if let Some(nonce) = nonce {
+            let key = (asset_id.clone(), chain_id.clone(), nonce.u128());
+            if USED_NONCES.has(deps.storage, key) {
+                return Err(ContractError::NonceAlreadyUsed {
+                    asset_id,
+                    chain_id,
+                    nonce,
+                });
+            }
+        }
+        let coin = Coin {
+            denom: asset.denom,
+            amount,
+        };
+        let send_tx = skyway::send_tx(&to, &coin, asset.decimals, &chain_id)?;
+
+        let transfer_id = TRANSFER_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        TRANSFER_COUNT.save(deps.storage, &transfer_id)?;
+        if let Some(nonce) = nonce {
+            USED_NONCES.save(
+                deps.storage,
+                (asset_id.clone(), chain_id.clone(), nonce.u128()),
+                &transfer_id,
+            )?;
+        }
+        transfers().save(
+            deps.storage,
+            transfer_id,
+            &Transfer {
+                id: transfer_id,
+                origin,
+                asset_id: asset_id.clone(),
+                chain_id: chain_id.clone(),
+                destination: to.clone(),
+                amount,
+                denom: coin.denom.clone(),
+                fee,
+                nonce,
+                sender: sender.clone(),
+                created_at: env.block.time,
+                status: TransferStatus::Pending,
+                updated_at: env.block.time,
+                skyway_tx_id: None,
+                refunded: None,
+            },
+        )?;
+        record_rate_limit_usage(deps.storage, &asset_id, &chain_id, now, amount)?;
+        record_user_usage(deps.storage, &asset_id, &sender, now, amount)?;
+        if !fee.is_zero() {
+            FEE_BALANCES.update(
+                deps.storage,
+                coin.denom.clone(),
+                |balance| -> StdResult<_> { Ok(balance.unwrap_or_default().checked_add(fee)?) },
+            )?;
+        }
+
+        // The reply links the Skyway transaction id to the transfer
+        let send_tx = SubMsg::reply_on_success(skyway::send_tx_msg(send_tx), SEND_TX_REPLY_ID)
+            .with_payload(to_json_binary(&transfer_id)?);
+        let mut response = Response::new()
+            .add_submessage(send_tx)
+            .add_attribute(
+                "action",
+                match origin {
+                    TransferOrigin::Operator => "send_pusd",
+                    TransferOrigin::Bridge => "bridge",
+                },
+            )
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("sender", sender)
+            .add_attribute("to", to)
+            .add_attribute("amount", coin.amount.to_string())
+            .add_attribute("denom", coin.denom)
+            .add_attribute("fee", fee.to_string());
+        if let Some(nonce) = nonce {
+            response = response.add_attribute("nonce", nonce.to_string());
+        }
+
+        Ok(response.add_attribute("transfer_id", transfer_id.to_string()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn send_pusd(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
+        asset_id: Option<String>,
         chain_id: String,
         to: String,
         amount: Uint128,
         nonce: Uint128,
     ) -> Result<Response<PalomaMsg>, ContractError> {
-        let state = STATE.load(deps.storage)?;
-        if info.sender != state.owner {
-            return Err(ContractError::Unauthorized {});
+        let asset = load_asset(deps.storage, asset_id)?;
+        issue_transfer(
+            deps,
+            &env,
+            TransferOrigin::Operator,
+            info.sender,
+            asset,
+            chain_id,
+            to,
+            amount,
+            Uint128::zero(),
+            Some(nonce),
+        )
+    }
+
+    pub fn bridge(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        asset_id: Option<String>,
+        chain_id: String,
+        to: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let asset = load_asset(deps.storage, asset_id)?;
+        let paid = paid_amount(&info, &asset.1.denom)?;
+        // The fee is deducted from the funds and the rest is bridged
+        let fee = bridge_fee(deps.storage, &asset.0, &chain_id, paid)?;
+        if !fee.is_zero() && fee >= paid {
+            return Err(ContractError::FeeExceedsAmount { amount: paid, fee });
         }
-        let amount: String =
-            amount.to_string() + "factory/" + state.pusd_manager.as_str() + "/upusd";
+        issue_transfer(
+            deps,
+            &env,
+            TransferOrigin::Bridge,
+            info.sender,
+            asset,
+            chain_id,
+            to,
+            paid - fee,
+            fee,
+            None,
+        )
+    }
 
-        let response = Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SkywayMsg {
-                send_tx: Some(SendTx {
-                    remote_chain_destination_address: to.clone(),
-                    amount: amount.clone(),
-                    chain_reference_id: chain_id.clone(),
-                }),
-                cancel_tx: None,
-            }))
-            .add_attribute("action", "send_pusd")
-            .add_attribute("chain_id", chain_id)
-            .add_attribute("to", to)
-            .add_attribute("amount", amount.to_string())
-            .add_attribute("nonce", nonce.to_string());
+    pub fn set_fee_schedule(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        chain_id: String,
+        fee_schedule: Option<FeeSchedule>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+        let key = (asset_id.clone(), chain_id.clone());
+        let mut response = Response::new()
+            .add_attribute("action", "set_fee_schedule")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("chain_id", chain_id);
+        match fee_schedule {
+            Some(fee_schedule) => {
+                if fee_schedule.bps > MAX_FEE_BPS {
+                    return Err(ContractError::InvalidFeeSchedule {
+                        reason: format!("bps above {MAX_FEE_BPS}"),
+                    });
+                }
+                if fee_schedule.max.is_some_and(|max| max < fee_schedule.min) {
+                    return Err(ContractError::InvalidFeeSchedule {
+                        reason: "max below min".to_string(),
+                    });
+                }
+                FEE_SCHEDULES.save(deps.storage, key, &fee_schedule)?;
+                response = response
+                    .add_attribute("flat", fee_schedule.flat.to_string())
+                    .add_attribute("bps", fee_schedule.bps.to_string());
+            }
+            None => FEE_SCHEDULES.remove(deps.storage, key),
+        }
+        Ok(response)
+    }
+
+    pub fn set_rate_limit(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        chain_id: String,
+        rate_limit: Option<RateLimit>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+        let key = (asset_id.clone(), chain_id.clone());
+        let mut response = Response::new()
+            .add_attribute("action", "set_rate_limit")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("chain_id", chain_id);
+        match rate_limit {
+            Some(rate_limit) => {
+                if rate_limit.window_seconds == 0 {
+                    return Err(ContractError::InvalidRateLimit {
+                        reason: "empty window".to_string(),
+                    });
+                }
+                RATE_LIMITS.save(deps.storage, key, &rate_limit)?;
+                response = response
+                    .add_attribute("window_seconds", rate_limit.window_seconds.to_string())
+                    .add_attribute("max_amount", rate_limit.max_amount.to_string());
+            }
+            None => {
+                RATE_LIMITS.remove(deps.storage, key.clone());
+                RATE_LIMIT_USAGE.remove(deps.storage, key);
+            }
+        }
+        Ok(response)
+    }
+
+    pub fn set_default_user_quota(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        quota: Option<Uint128>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let mut response = Response::new()
+            .add_attribute("action", "set_default_user_quota")
+            .add_attribute("asset_id", asset_id.clone());
+        match quota {
+            Some(quota) => {
+                DEFAULT_USER_QUOTAS.save(deps.storage, asset_id, &quota)?;
+                response = response.add_attribute("quota", quota.to_string());
+            }
+            None => DEFAULT_USER_QUOTAS.remove(deps.storage, asset_id),
+        }
+        Ok(response)
+    }
 
+    pub fn set_user_quota(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        address: String,
+        quota: Option<Uint128>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let address = deps.api.addr_validate(&address)?;
+        let key = (asset_id.clone(), address.clone());
+        let mut response = Response::new()
+            .add_attribute("action", "set_user_quota")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("address", address);
+        match quota {
+            Some(quota) => {
+                USER_QUOTAS.save(deps.storage, key, &quota)?;
+                response = response.add_attribute("quota", quota.to_string());
+            }
+            None => USER_QUOTAS.remove(deps.storage, key),
+        }
         Ok(response)
     }
 
+    pub fn set_sender_denied(
+        deps: DepsMut,
+        address: String,
+        denied: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let address = deps.api.addr_validate(&address)?;
+        if denied {
+            DENIED_SENDERS.save(deps.storage, address.clone(), &())?;
+        } else {
+            DENIED_SENDERS.remove(deps.storage, address.clone());
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_sender_denied")
+            .add_attribute("address", address)
+            .add_attribute("denied", denied.to_string()))
+    }
+
+    pub fn set_destination_denied(
+        deps: DepsMut,
+        chain_id: String,
+        address: String,
+        denied: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let key = (chain_id.clone(), address::canonical(&address));
+        if denied {
+            DENIED_DESTINATIONS.save(deps.storage, key, &())?;
+        } else {
+            DENIED_DESTINATIONS.remove(deps.storage, key);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_destination_denied")
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("address", address)
+            .add_attribute("denied", denied.to_string()))
+    }
+
+    pub fn set_destination_allowed(
+        deps: DepsMut,
+        chain_id: String,
+        address: String,
+        allowed: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let key = (chain_id.clone(), address::canonical(&address));
+        if allowed {
+            ALLOWED_DESTINATIONS.save(deps.storage, key, &())?;
+        } else {
+            ALLOWED_DESTINATIONS.remove(deps.storage, key);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_destination_allowed")
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("address", address)
+            .add_attribute("allowed", allowed.to_string()))
+    }
+
+    pub fn set_allowlist_mode(
+        deps: DepsMut,
+        chain_id: String,
+        enabled: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        if enabled {
+            ALLOWLIST_CHAINS.save(deps.storage, chain_id.clone(), &())?;
+        } else {
+            ALLOWLIST_CHAINS.remove(deps.storage, chain_id.clone());
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_allowlist_mode")
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    pub fn claim_fees(
+        deps: DepsMut,
+        info: MessageInfo,
+        asset_id: Option<String>,
+        recipient: Option<String>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, asset) = load_asset(deps.storage, asset_id)?;
+        let recipient = match recipient {
+            Some(recipient) => deps.api.addr_validate(&recipient)?,
+            None => info.sender,
+        };
+        let amount = FEE_BALANCES
+            .may_load(deps.storage, asset.denom.clone())?
+            .unwrap_or_default();
+        if amount.is_zero() {
+            return Err(ContractError::NoFeesToClaim { denom: asset.denom });
+        }
+        FEE_BALANCES.remove(deps.storage, asset.denom.clone());
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: asset.denom.clone(),
+                    amount,
+                }],
+            })
+            .add_attribute("action", "claim_fees")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("recipient", recipient)
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("denom", asset.denom))
+    }
+
     pub fn withdraw_pusd(
         deps: DepsMut,
         info: MessageInfo,
+        asset_id: Option<String>,
         chain_id: String,
         recipient: String,
         amount: Uint128,
     ) -> Result<Response<PalomaMsg>, ContractError> {
-        let state = STATE.load(deps.storage)?;
-        if info.sender != state.owner {
-            return Err(ContractError::Unauthorized {});
-        }
-        let pusd_manager = state.pusd_manager;
-        let pusd_denom: String = "factory/".to_string() + pusd_manager.as_str() + "/upusd";
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, asset) = load_asset(deps.storage, asset_id)?;
+        load_active_chain(deps.storage, &asset_id, &chain_id)?;
+        validate_remote_address(deps.storage, &asset_id, &chain_id, &recipient)?;
+        ensure_sender_allowed(deps.storage, &info.sender)?;
+        ensure_destination_allowed(deps.storage, &chain_id, &recipient)?;
         Ok(Response::new()
             .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: pusd_manager.to_string(),
+                contract_addr: asset.manager.to_string(),
                 msg: to_json_binary(&ExternalExecuteMsg::Withdraw {
                     chain_id,
                     recipient,
                 })?,
                 funds: vec![Coin {
-                    denom: pusd_denom,
+                    denom: asset.denom,
                     amount,
                 }],
             }))
-            .add_attribute("action", "withdraw_pusd"))
+            .add_attribute("action", "withdraw_pusd")
+            .add_attribute("asset_id", asset_id))
     }
 
-    pub fn cancel_tx(
+    pub fn manage_withdraw(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
-        transaction_id: u64,
+        asset_id: Option<String>,
+        nonce: u64,
+        kind: WithdrawActionKind,
     ) -> Result<Response<PalomaMsg>, ContractError> {
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
+        let (asset_id, asset) = load_asset(deps.storage, asset_id)?;
+        let (msg, action) = match kind {
+            // Retrying moves funds again, while cancelling stays possible during an incident
+            WithdrawActionKind::ReWithdraw => {
+                ensure_not_paused_globally(deps.storage)?;
+                ensure_sender_allowed(deps.storage, &info.sender)?;
+                (ExternalExecuteMsg::ReWithdraw { nonce }, "re_withdraw_pusd")
+            }
+            WithdrawActionKind::CancelWithdraw => (
+                ExternalExecuteMsg::CancelWithdraw { nonce },
+                "cancel_withdraw_pusd",
+            ),
+        };
+        let mut actions = WITHDRAW_ACTIONS
+            .may_load(deps.storage, (asset_id.clone(), nonce))?
+            .unwrap_or_default();
+        actions.push(WithdrawAction {
+            kind,
+            sender: info.sender.clone(),
+            height: env.block.height,
+            time: env.block.time,
+        });
+        WITHDRAW_ACTIONS.save(deps.storage, (asset_id.clone(), nonce), &actions)?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SkywayMsg {
-                send_tx: None,
-                cancel_tx: Some(CancelTx { transaction_id }),
+            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: asset.manager.to_string(),
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
             }))
-            .add_attribute("action", "cancel_tx"))
+            .add_attribute("action", action)
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("nonce", nonce.to_string())
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn cancel_tx(
+        deps: DepsMut,
+        env: Env,
+        transaction_id: u64,
+        transfer_id: Option<u64>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let mut response = Response::new()
+            .add_message(skyway::cancel_tx_msg(transaction_id))
+            .add_attribute("action", "cancel_tx")
+            .add_attribute("transaction_id", transaction_id.to_string());
+        let transfer_id = match transfer_id {
+            Some(transfer_id) => {
+                // An explicit transfer must be the one sent under this transaction
+                let transfer = load_transfer(deps.storage, transfer_id)?;
+                if transfer.skyway_tx_id != Some(transaction_id) {
+                    return Err(ContractError::TransactionMismatch {
+                        id: transfer_id,
+                        transaction_id,
+                    });
+                }
+                Some(transfer_id)
+            }
+            None => SKYWAY_TXS.may_load(deps.storage, transaction_id)?,
+        };
+        if let Some(transfer_id) = transfer_id {
+            let (transfer, refund) =
+                update_transfer_status(deps.storage, &env, transfer_id, TransferStatus::Cancelled)?;
+            response = response.add_attribute("transfer_id", transfer_id.to_string());
+            if let Some(refund) = refund {
+                response = response
+                    .add_message(refund)
+                    .add_attribute("refunded", transfer.refunded.unwrap_or_default());
+            }
+        }
+        Ok(response)
+    }
+
+    pub fn cancel_transfer(
+        deps: DepsMut,
+        env: Env,
+        chain_id: String,
+        asset_id: Option<String>,
+        nonce: Uint128,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let transfer_id = USED_NONCES
+            .may_load(
+                deps.storage,
+                (asset_id.clone(), chain_id.clone(), nonce.u128()),
+            )?
+            .ok_or(ContractError::NonceNotFound {
+                asset_id,
+                chain_id,
+                nonce,
+            })?;
+        let transaction_id = load_transfer(deps.storage, transfer_id)?
+            .skyway_tx_id
+            .ok_or(ContractError::TransactionIdUnknown { id: transfer_id })?;
+        cancel_tx(deps, env, transaction_id, Some(transfer_id))
+    }
+
+    pub fn register_asset(
+        deps: DepsMut,
+        asset_id: String,
+        manager: String,
+        denom: Option<String>,
+        decimals: u8,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let manager = deps.api.addr_validate(&manager)?;
+        let denom = denom.unwrap_or_else(|| default_pusd_denom(&manager));
+        skyway::validate_denom(&denom)?;
+        skyway::validate_decimals(decimals)?;
+        if let Some(existing) = ASSETS.may_load(deps.storage, asset_id.clone())? {
+            if existing.denom != denom {
+                ensure_denom_unused(deps.storage, &asset_id, &existing.denom)?;
+            }
+        }
+        ASSETS.save(
+            deps.storage,
+            asset_id.clone(),
+            &Asset {
+                denom: denom.clone(),
+                manager: manager.clone(),
+                decimals,
+            },
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "register_asset")
+            .add_attribute("asset_id", asset_id)
+            .add_attribute("denom", denom)
+            .add_attribute("manager", manager)
+            .add_attribute("decimals", decimals.to_string()))
     }
 
     pub fn change_config(
         deps: DepsMut,
-        info: MessageInfo,
-        owner: Option<Addr>,
-        pusd_manager: Option<Addr>,
+        default_asset: Option<String>,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let mut state = STATE.load(deps.storage)?;
+        let mut response: Response<PalomaMsg> =
+            Response::new().add_attribute("action", "change_config");
+
+        if let Some(default_asset) = default_asset {
+            let (default_asset, _) = load_asset(deps.storage, Some(default_asset))?;
+            state.default_asset = default_asset.clone();
+            response = response.add_attribute("new_default_asset", default_asset);
+        }
+        STATE.save(deps.storage, &state)?;
+        Ok(response)
+    }
+
+    pub fn propose_owner(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expires_at: Option<Timestamp>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
         if info.sender != state.owner {
             return Err(ContractError::Unauthorized {});
         }
-        let mut response: Response<PalomaMsg> =
-            Response::new().add_attribute("action", "change_config");
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.block.time {
+                return Err(ContractError::InvalidExpiry {});
+            }
+        }
+        PENDING_OWNER.save(
+            deps.storage,
+            &PendingOwner {
+                owner: new_owner.clone(),
+                expires_at,
+            },
+        )?;
+        let mut response = Response::new()
+            .add_attribute("action", "propose_owner")
+            .add_attribute("pending_owner", new_owner);
+        if let Some(expires_at) = expires_at {
+            response = response.add_attribute("expires_at", expires_at.to_string());
+        }
+        Ok(response)
+    }
 
-        if let Some(owner) = owner {
-            state.owner = owner.clone();
-            response = response.add_attribute("new_owner", owner.to_string());
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let pending = PENDING_OWNER
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingOwner {})?;
+        if info.sender != pending.owner {
+            return Err(ContractError::Unauthorized {});
         }
-        if let Some(pusd_manager) = pusd_manager {
-            state.pusd_manager = pusd_manager.clone();
-            response = response.add_attribute("new_pusd_manager", pusd_manager.to_string());
+        if let Some(expires_at) = pending.expires_at {
+            if env.block.time >= expires_at {
+                return Err(ContractError::OwnershipProposalExpired {});
+            }
         }
+        let mut state = STATE.load(deps.storage)?;
+        let previous_owner = state.owner;
+        state.owner = pending.owner;
         STATE.save(deps.storage, &state)?;
+        PENDING_OWNER.remove(deps.storage);
+        Ok(Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("previous_owner", previous_owner)
+            .add_attribute("new_owner", state.owner))
+    }
+
+    pub fn cancel_ownership_proposal(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if info.sender != state.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !PENDING_OWNER.exists(deps.storage) {
+            return Err(ContractError::NoPendingOwner {});
+        }
+        PENDING_OWNER.remove(deps.storage);
+        Ok(Response::new().add_attribute("action", "cancel_ownership_proposal"))
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        role: Role,
+        address: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let address = deps.api.addr_validate(&address)?;
+        ROLES.save(deps.storage, (role, address.clone()), &())?;
+        Ok(Response::new()
+            .add_attribute("action", "grant_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address))
+    }
+
+    pub fn revoke_role(
+        deps: DepsMut,
+        role: Role,
+        address: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let address = deps.api.addr_validate(&address)?;
+        if !ROLES.has(deps.storage, (role, address.clone())) {
+            return Err(ContractError::RoleNotGranted {
+                role: role.to_string(),
+                address: address.to_string(),
+            });
+        }
+        ROLES.remove(deps.storage, (role, address.clone()));
+        Ok(Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address))
+    }
+
+    pub fn renounce_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        if !ROLES.has(deps.storage, (role, info.sender.clone())) {
+            return Err(ContractError::RoleNotGranted {
+                role: role.to_string(),
+                address: info.sender.to_string(),
+            });
+        }
+        ROLES.remove(deps.storage, (role, info.sender.clone()));
+        Ok(Response::new()
+            .add_attribute("action", "renounce_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", info.sender))
+    }
+
+    pub fn set_paused(
+        deps: DepsMut,
+        chain_id: Option<String>,
+        paused: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let mut response =
+            Response::new().add_attribute("action", if paused { "pause" } else { "unpause" });
+        match chain_id {
+            Some(chain_id) => {
+                if paused {
+                    PAUSED_CHAINS.save(deps.storage, chain_id.clone(), &())?;
+                } else {
+                    PAUSED_CHAINS.remove(deps.storage, chain_id.clone());
+                }
+                response = response.add_attribute("chain_id", chain_id);
+            }
+            None => PAUSED.save(deps.storage, &paused)?,
+        }
         Ok(response)
     }
 
     pub fn set_paloma(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement SetPaloma
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "set_paloma".to_string(),
-                vec![Function {
-                    name: "set_paloma".to_string(),
-                    inputs: vec![],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let payload = abi::encode("set_paloma", &[])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.paloma_set = Some(Recorded::new(true, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("set_paloma")
-                            .unwrap()
-                            .encode_input(&[])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "set_paloma"))
     }
 
+    pub fn execute_remote(
+        deps: DepsMut,
+        asset_id: Option<String>,
+        chain_id: String,
+        function_name: String,
+        args: Vec<AbiArg>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let chain_setting = load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+        let (function, tokens) =
+            abi::dynamic_function(&chain_setting.chain_kind, &function_name, &args)?;
+        let payload = abi::encode_function(&function, &tokens)?;
+        Ok(Response::new()
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
+            .add_attribute("action", "execute_remote")
+            .add_attribute("chain_id", chain_id)
+            .add_attribute("function", function.signature()))
+    }
+
     pub fn update_withdraw_limit(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_withdraw_limit: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdateWithdrawLimit
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_withdraw_limit: Uint = Uint::from_big_endian(&new_withdraw_limit.to_be_bytes());
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_withdraw_limit".to_string(),
-                vec![Function {
-                    name: "update_withdraw_limit".to_string(),
-                    inputs: vec![Param {
-                        name: "new_withdraw_limit".to_string(),
-                        kind: ParamType::Uint(256),
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let payload = abi::encode(
+            "update_withdraw_limit",
+            &[abi::uint256_token(new_withdraw_limit)],
+        )?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.withdraw_limit = Some(Recorded::new(new_withdraw_limit, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_withdraw_limit")
-                            .unwrap()
-                            .encode_input(&[Token::Uint(new_withdraw_limit)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_withdraw_limit"))
     }
 
     pub fn update_pusd(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_pusd: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdatePusd
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_pusd_address: Address = Address::from_str(new_pusd.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_pusd".to_string(),
-                vec![Function {
-                    name: "update_pusd".to_string(),
-                    inputs: vec![Param {
-                        name: "new_pusd".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let address = remote_abi_address(deps.storage, &asset_id, &chain_id, &new_pusd)?;
+        let payload = abi::encode("update_pusd", &[Token::Address(address)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.pusd = Some(Recorded::new(new_pusd, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_pusd")
-                            .unwrap()
-                            .encode_input(&[Token::Address(new_pusd_address)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_pusd"))
     }
 
     pub fn update_pusd_manager(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_pusd_manager: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdatePusdManager
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_pusd_manager_address: Address =
-            Address::from_str(new_pusd_manager.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_pusd_manager".to_string(),
-                vec![Function {
-                    name: "update_pusd_manager".to_string(),
-                    inputs: vec![Param {
-                        name: "new_pusd_manager".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let address = remote_abi_address(deps.storage, &asset_id, &chain_id, &new_pusd_manager)?;
+        let payload = abi::encode("update_pusd_manager", &[Token::Address(address)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.pusd_manager = Some(Recorded::new(new_pusd_manager, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_pusd_manager")
-                            .unwrap()
-                            .encode_input(&[Token::Address(new_pusd_manager_address)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_pusd_manager"))
     }
 
     pub fn update_refund_wallet(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_refund_wallet: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
-        let state = STATE.load(deps.storage)?;
-        assert!(state.owner == info.sender, "Unauthorized");
-        let new_refund_wallet_address: Address =
-            Address::from_str(new_refund_wallet.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_refund_wallet".to_string(),
-                vec![Function {
-                    name: "update_refund_wallet".to_string(),
-                    inputs: vec![Param {
-                        name: "new_refund_wallet".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let address = remote_abi_address(deps.storage, &asset_id, &chain_id, &new_refund_wallet)?;
+        let payload = abi::encode("update_refund_wallet", &[Token::Address(address)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.refund_wallet = Some(Recorded::new(new_refund_wallet, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_refund_wallet")
-                            .unwrap()
-                            .encode_input(&[Token::Address(new_refund_wallet_address)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_refund_wallet"))
     }
+
     pub fn update_gas_fee(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_gas_fee: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdateGasFee
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_gas_fee: Uint = Uint::from_big_endian(&new_gas_fee.to_be_bytes());
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_gas_fee".to_string(),
-                vec![Function {
-                    name: "update_gas_fee".to_string(),
-                    inputs: vec![Param {
-                        name: "new_gas_fee".to_string(),
-                        kind: ParamType::Uint(256),
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let payload = abi::encode("update_gas_fee", &[abi::uint256_token(new_gas_fee)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.gas_fee = Some(Recorded::new(new_gas_fee, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_gas_fee")
-                            .unwrap()
-                            .encode_input(&[Token::Uint(new_gas_fee)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_gas_fee"))
     }
+
     pub fn update_service_fee_collector(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_service_fee_collector: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdateServiceFeeCollector
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_service_fee_collector: Address =
-            Address::from_str(new_service_fee_collector.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_service_fee_collector".to_string(),
-                vec![Function {
-                    name: "update_service_fee_collector".to_string(),
-                    inputs: vec![Param {
-                        name: "new_service_fee_collector".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let address = remote_abi_address(
+            deps.storage,
+            &asset_id,
+            &chain_id,
+            &new_service_fee_collector,
+        )?;
+        let payload = abi::encode("update_service_fee_collector", &[Token::Address(address)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.service_fee_collector =
+                Some(Recorded::new(new_service_fee_collector, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_service_fee_collector")
-                            .unwrap()
-                            .encode_input(&[Token::Address(new_service_fee_collector)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_service_fee_collector"))
     }
+
     pub fn update_service_fee(
         deps: DepsMut,
-        info: MessageInfo,
+        env: Env,
+        asset_id: Option<String>,
         chain_id: String,
         new_service_fee: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         // ACTION: Implement UpdateServiceFee
-        let state = STATE.load(deps.storage)?;
-        assert!(info.sender == state.owner, "Unauthorized");
-        let new_service_fee: Uint = Uint::from_big_endian(&new_service_fee.to_be_bytes());
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_service_fee".to_string(),
-                vec![Function {
-                    name: "update_service_fee".to_string(),
-                    inputs: vec![Param {
-                        name: "new_service_fee".to_string(),
-                        kind: ParamType::Uint(256),
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
+        ensure_not_paused(deps.storage, &chain_id)?;
+        let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+        let payload = abi::encode("update_service_fee", &[abi::uint256_token(new_service_fee)])?;
+        record_remote(deps.storage, &asset_id, &chain_id, |remote| {
+            remote.service_fee = Some(Recorded::new(new_service_fee, &env.block))
+        })?;
         Ok(Response::new()
-            .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                execute_job: ExecuteJob {
-                    job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("update_service_fee")
-                            .unwrap()
-                            .encode_input(&[Token::Uint(new_service_fee)])
-                            .unwrap(),
-                    ),
-                },
-            }))
+            .add_message(remote_job(deps.storage, &asset_id, &chain_id, payload)?)
             .add_attribute("action", "update_service_fee"))
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response<PalomaMsg>, ContractError> {
+    match msg.id {
+        SEND_TX_REPLY_ID => reply::send_tx(deps, msg),
+        id => Err(ContractError::UnknownReply { id }),
+    }
+}
+
+pub mod reply {
+    use cosmwasm_std::{from_json, Event, StdError, SubMsgResponse};
+
+    use super::*;
+    use crate::state::SKYWAY_TXS;
+
+    // Typed event Skyway emits for a new outgoing transaction. Typed event values are JSON
+    // encoded, so the id comes quoted.
+    pub const TX_ID_EVENT: &str = "palomachain.paloma.skyway.EventOutgoingTxId";
+    const TX_ID_ATTRIBUTE: &str = "tx_id";
+    // Emitted when the send_tx response carries no transaction id
+    pub const TX_ID_MISSING_EVENT: &str = "skyway_tx_id_missing";
+
+    pub fn transaction_id(response: &SubMsgResponse) -> Option<u64> {
+        response
+            .events
+            .iter()
+            .filter(|event| event.ty == TX_ID_EVENT)
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == TX_ID_ATTRIBUTE)
+            .and_then(|attribute| attribute.value.trim_matches('"').parse().ok())
+    }
+
+    pub fn send_tx(deps: DepsMut, msg: Reply) -> Result<Response<PalomaMsg>, ContractError> {
+        let transfer_id: u64 = from_json(&msg.payload)?;
+        let result = msg.result.into_result().map_err(StdError::generic_err)?;
+        let response = Response::new()
+            .add_attribute("action", "send_tx_reply")
+            .add_attribute("transfer_id", transfer_id.to_string());
+        // Failing here would revert the send itself, so a missing id only leaves the transfer
+        // without its Skyway transaction id and flags it for the indexer
+        let transaction_id = match transaction_id(&result) {
+            Some(transaction_id) => transaction_id,
+            None => {
+                return Ok(response.add_event(
+                    Event::new(TX_ID_MISSING_EVENT)
+                        .add_attribute("transfer_id", transfer_id.to_string()),
+                ))
+            }
+        };
+        let mut transfer = load_transfer(deps.storage, transfer_id)?;
+        transfer.skyway_tx_id = Some(transaction_id);
+        transfers().save(deps.storage, transfer_id, &transfer)?;
+        SKYWAY_TXS.save(deps.storage, transaction_id, &transfer_id)?;
+        Ok(response.add_attribute("transaction_id", transaction_id.to_string()))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
     match msg {
+        SudoMsg::TransferConfirmed { transaction_id } => {
+            sudo::settle_transfer(deps, env, transaction_id, TransferStatus::Confirmed)
+        }
+        SudoMsg::TransferRefunded { transaction_id } => {
+            sudo::settle_transfer(deps, env, transaction_id, TransferStatus::Refunded)
+        }
+        SudoMsg::JobExecuted { job_id, chain_id } => {
+            sudo::record_job(deps, env, job_id, chain_id, None)
+        }
+        SudoMsg::JobFailed {
+            job_id,
+            chain_id,
+            error,
+        } => sudo::record_job(deps, env, job_id, chain_id, Some(error)),
+    }
+}
+
+pub mod sudo {
+    use super::*;
+    use crate::state::{JobResult, SKYWAY_TXS};
+
+    pub fn settle_transfer(
+        deps: DepsMut,
+        env: Env,
+        transaction_id: u64,
+        status: TransferStatus,
+    ) -> Result<Response, ContractError> {
+        let transfer_id = SKYWAY_TXS
+            .may_load(deps.storage, transaction_id)?
+            .ok_or(ContractError::UnknownTransaction { transaction_id })?;
+        let (transfer, refund) = update_transfer_status(deps.storage, &env, transfer_id, status)?;
+        let mut response = Response::new()
+            .add_attribute("action", "transfer_".to_string() + status.as_str())
+            .add_attribute("transfer_id", transfer_id.to_string())
+            .add_attribute("transaction_id", transaction_id.to_string())
+            .add_attribute("chain_id", transfer.chain_id);
+        if let Some(nonce) = transfer.nonce {
+            response = response.add_attribute("nonce", nonce.to_string());
+        }
+        if let Some(refund) = refund {
+            response = response
+                .add_message(refund)
+                .add_attribute("refunded", transfer.refunded.unwrap_or_default());
+        }
+        Ok(response)
+    }
+
+    pub fn record_job(
+        deps: DepsMut,
+        env: Env,
+        job_id: String,
+        chain_id: String,
+        error: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let success = error.is_none();
+        JOB_RESULTS.save(
+            deps.storage,
+            job_id.clone(),
+            &JobResult {
+                chain_id: chain_id.clone(),
+                success,
+                error: error.clone(),
+                height: env.block.height,
+                time: env.block.time,
+            },
+        )?;
+        let mut response = Response::new()
+            .add_attribute(
+                "action",
+                if success {
+                    "job_executed"
+                } else {
+                    "job_failed"
+                },
+            )
+            .add_attribute("job_id", job_id)
+            .add_attribute("chain_id", chain_id);
+        if let Some(error) = error {
+            response = response.add_attribute("error", error);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    Ok(match msg {
         QueryMsg::GetState {} => {
             let state = STATE.load(deps.storage)?;
-            to_json_binary(&state)
+            to_json_binary(&state)?
         }
-        QueryMsg::GetChainSettings {} => {
-            let mut chain_setting_info: Vec<ChainSettingInfo> = Vec::new();
-            CHAIN_SETTINGS
+        QueryMsg::GetAsset { asset_id } => {
+            let (asset_id, asset) = load_asset(deps.storage, Some(asset_id))?;
+            to_json_binary(&AssetInfo::new(asset_id, asset))?
+        }
+        QueryMsg::GetAssets { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+            let assets = ASSETS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(asset_id, asset)| AssetInfo::new(asset_id, asset)))
+                .collect::<StdResult<Vec<AssetInfo>>>()?;
+            to_json_binary(&assets)?
+        }
+        QueryMsg::GetChainSetting { chain_id, asset_id } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            let chain_setting = load_chain_setting(deps.storage, &asset_id, &chain_id)?;
+            to_json_binary(&ChainSettingInfo::new(asset_id, chain_id, chain_setting))?
+        }
+        QueryMsg::GetChainSettings {
+            asset_id,
+            start_after,
+            limit,
+        } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+            let chain_setting_info = CHAIN_SETTINGS
+                .prefix(asset_id.clone())
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(chain_id, chain_setting)| {
+                        ChainSettingInfo::new(asset_id.clone(), chain_id, chain_setting)
+                    })
+                })
+                .collect::<StdResult<Vec<ChainSettingInfo>>>()?;
+            to_json_binary(&chain_setting_info)?
+        }
+        QueryMsg::GetOwnership {} => {
+            let state = STATE.load(deps.storage)?;
+            let pending = PENDING_OWNER.may_load(deps.storage)?;
+            to_json_binary(&OwnershipResponse {
+                owner: state.owner,
+                pending_owner: pending.as_ref().map(|p| p.owner.clone()),
+                pending_expires_at: pending.and_then(|p| p.expires_at),
+            })?
+        }
+        QueryMsg::HasRole { role, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&has_role(deps.storage, &address, role)?)?
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?
+                .map(Bound::exclusive);
+            let members = ROLES
+                .prefix(role)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<Addr>>>()?;
+            to_json_binary(&members)?
+        }
+        QueryMsg::GetPauseStatus {} => {
+            let paused_chains = PAUSED_CHAINS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<String>>>()?;
+            to_json_binary(&PauseStatusResponse {
+                paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+                paused_chains,
+            })?
+        }
+        QueryMsg::GetFeeSchedule { chain_id, asset_id } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            to_json_binary(&FEE_SCHEDULES.may_load(deps.storage, (asset_id, chain_id))?)?
+        }
+        QueryMsg::GetFeeBalances {} => {
+            let balances = FEE_BALANCES
                 .range(deps.storage, None, None, Order::Ascending)
-                .for_each(|item| {
-                    let item = item.unwrap();
-                    chain_setting_info.push(ChainSettingInfo {
-                        chain_id: item.clone().0,
-                        job_id: item.1.job_id.clone(),
-                    });
-                });
-            to_json_binary(&chain_setting_info)
+                .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+                .collect::<StdResult<Vec<Coin>>>()?;
+            to_json_binary(&balances)?
         }
-    }
+        QueryMsg::QuoteSend {
+            chain_id,
+            asset_id,
+            amount,
+            sender,
+            to,
+        } => {
+            let sender = sender
+                .map(|sender| deps.api.addr_validate(&sender))
+                .transpose()?;
+            to_json_binary(&quote_send(
+                deps.storage,
+                env.block.time.seconds(),
+                asset_id,
+                chain_id,
+                amount,
+                sender,
+                to,
+            )?)?
+        }
+        QueryMsg::GetUserQuota { address, asset_id } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&user_quota_status(
+                deps.storage,
+                &asset_id,
+                &address,
+                env.block.time.seconds(),
+            )?)?
+        }
+        QueryMsg::IsSenderDenied { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&DENIED_SENDERS.has(deps.storage, address))?
+        }
+        QueryMsg::GetDestinationStatus { chain_id, address } => {
+            to_json_binary(&destination_status(deps.storage, &chain_id, &address))?
+        }
+        QueryMsg::GetRateLimit { chain_id, asset_id } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            to_json_binary(&rate_limit_status(
+                deps.storage,
+                &asset_id,
+                &chain_id,
+                env.block.time.seconds(),
+            )?)?
+        }
+        QueryMsg::IsNonceUsed {
+            chain_id,
+            asset_id,
+            nonce,
+        } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            to_json_binary(&USED_NONCES.has(deps.storage, (asset_id, chain_id, nonce.u128())))?
+        }
+        QueryMsg::GetTransfer { id } => to_json_binary(&load_transfer(deps.storage, id)?)?,
+        QueryMsg::GetTransfers {
+            filter,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+            let transfers = transfers();
+            let items = match filter {
+                None => transfers.range(deps.storage, start, None, Order::Ascending),
+                Some(TransferFilter::ChainId(chain_id)) => transfers
+                    .idx
+                    .chain_id
+                    .prefix(chain_id)
+                    .range(deps.storage, start, None, Order::Ascending),
+                Some(TransferFilter::Destination(destination)) => transfers
+                    .idx
+                    .destination
+                    .prefix(address::canonical(&destination))
+                    .range(deps.storage, start, None, Order::Ascending),
+                Some(TransferFilter::Status(status)) => transfers
+                    .idx
+                    .status
+                    .prefix(status.as_str().to_string())
+                    .range(deps.storage, start, None, Order::Ascending),
+            };
+            let items = items
+                .take(limit)
+                .map(|item| item.map(|(_, transfer)| transfer))
+                .collect::<StdResult<Vec<Transfer>>>()?;
+            to_json_binary(&items)?
+        }
+        QueryMsg::GetJobResult { job_id } => {
+            to_json_binary(&JOB_RESULTS.may_load(deps.storage, job_id)?)?
+        }
+        QueryMsg::GetWithdrawActions { nonce, asset_id } => {
+            let (asset_id, _) = load_asset(deps.storage, asset_id)?;
+            to_json_binary(
+                &WITHDRAW_ACTIONS
+                    .may_load(deps.storage, (asset_id, nonce))?
+                    .unwrap_or_default(),
+            )?
+        }
+        QueryMsg::GetRemoteFunctions {} => {
+            let functions: Vec<RemoteFunctionInfo> = abi::REMOTE_FUNCTIONS
+                .iter()
+                .map(abi::RemoteFunction::info)
+                .collect();
+            to_json_binary(&functions)?
+        }
+    })
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,77 @@
+use cosmwasm_std::Uint128;
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::AppResponse;
+
+use super::{contract_error, Suite, CHAIN_ID, DESTINATION};
+use crate::msg::{AssetInfo, ExecuteMsg, QueryMsg, SudoMsg};
+use crate::ContractError;
+
+fn set_denom(suite: &mut Suite, asset_id: &str, denom: &str) -> AnyResult<AppResponse> {
+    let owner = suite.owner.clone();
+    let asset: AssetInfo = suite.query(QueryMsg::GetAsset {
+        asset_id: asset_id.to_string(),
+    });
+    suite.execute(
+        &owner,
+        ExecuteMsg::RegisterAsset {
+            asset_id: asset_id.to_string(),
+            manager: asset.manager.to_string(),
+            denom: Some(denom.to_string()),
+            decimals: asset.decimals,
+        },
+    )
+}
+
+#[test]
+fn denom_is_locked_while_its_transfers_are_pending() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let contract = suite.contract.clone();
+    let manager = suite.addr("other_manager");
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::RegisterAsset {
+                asset_id: "other".to_string(),
+                manager: manager.to_string(),
+                denom: None,
+                decimals: 6,
+            },
+        )
+        .unwrap();
+    suite.mint(&contract, 100);
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::SendPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+                amount: Uint128::new(100),
+                nonce: Uint128::one(),
+            },
+        )
+        .unwrap();
+
+    // Pending transfers of another asset do not matter
+    set_denom(&mut suite, "other", "uother").unwrap();
+    let err = contract_error(set_denom(&mut suite, "pusd", "unew"));
+    assert!(matches!(
+        err,
+        ContractError::AssetInUse { asset_id, reason }
+            if asset_id == "pusd" && reason == "pending transfers"
+    ));
+
+    let tx_id = suite.last_tx_id();
+    suite.batch(tx_id);
+    suite
+        .sudo(SudoMsg::TransferConfirmed {
+            transaction_id: tx_id,
+        })
+        .unwrap();
+    set_denom(&mut suite, "pusd", "unew").unwrap();
+    let asset: AssetInfo = suite.query(QueryMsg::GetAsset {
+        asset_id: "pusd".to_string(),
+    });
+    assert_eq!(asset.denom, "unew");
+}
@@ -0,0 +1,299 @@
+use cosmwasm_std::{coin, coins, Addr, Coin, Uint128};
+
+use super::{contract_error, skyway_escrow, Suite, CHAIN_ID, DESTINATION};
+use crate::msg::{ExecuteMsg, QueryMsg, SudoMsg};
+use crate::state::{FeeSchedule, Transfer, TransferOrigin, TransferStatus};
+use crate::ContractError;
+
+const PAID: u128 = 10_000;
+// 1% of PAID
+const FEE: u128 = 100;
+
+fn bridge_msg() -> ExecuteMsg {
+    ExecuteMsg::Bridge {
+        chain_id: CHAIN_ID.to_string(),
+        asset_id: None,
+        to: DESTINATION.to_string(),
+    }
+}
+
+// A user bridging PAID with a 1% fee on CHAIN_ID
+fn bridged() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::SetFeeSchedule {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                fee_schedule: Some(FeeSchedule {
+                    flat: Uint128::zero(),
+                    bps: 100,
+                    min: Uint128::zero(),
+                    max: None,
+                }),
+            },
+        )
+        .unwrap();
+    let user = suite.addr("user");
+    suite.mint(&user, PAID);
+    let funds = coins(PAID, &suite.denom);
+    suite
+        .execute_with_funds(&user, bridge_msg(), &funds)
+        .unwrap();
+    (suite, user)
+}
+
+fn transfer(suite: &Suite, id: u64) -> Transfer {
+    suite.query(QueryMsg::GetTransfer { id })
+}
+
+fn fee_balances(suite: &Suite) -> Vec<Coin> {
+    suite.query(QueryMsg::GetFeeBalances {})
+}
+
+#[test]
+fn bridge_requires_a_single_coin_of_the_asset() {
+    let mut suite = Suite::new();
+    let user = suite.addr("user");
+    suite.mint(&user, PAID);
+    suite
+        .app
+        .sudo(
+            cw_multi_test::BankSudo::Mint {
+                to_address: user.to_string(),
+                amount: coins(PAID, "uother"),
+            }
+            .into(),
+        )
+        .unwrap();
+    let denom = suite.denom.clone();
+    for funds in [
+        vec![],
+        coins(PAID, "uother"),
+        vec![coin(PAID / 2, &denom), coin(PAID / 2, "uother")],
+    ] {
+        let err = contract_error(suite.execute_with_funds(&user, bridge_msg(), &funds));
+        assert!(matches!(err, ContractError::InvalidFunds { denom: d } if d == denom));
+    }
+}
+
+#[test]
+fn bridge_splits_the_fee_from_the_funds() {
+    let (suite, user) = bridged();
+    let transfer = transfer(&suite, 1);
+    assert_eq!(transfer.origin, TransferOrigin::Bridge);
+    assert_eq!(transfer.sender, user);
+    assert_eq!(transfer.amount, Uint128::new(PAID - FEE));
+    assert_eq!(transfer.fee, Uint128::new(FEE));
+    assert_eq!(transfer.status, TransferStatus::Pending);
+    assert_eq!(transfer.skyway_tx_id, Some(suite.last_tx_id()));
+    assert_eq!(fee_balances(&suite), coins(FEE, &suite.denom));
+    // The net amount left with Skyway, the fee stays with the contract
+    assert_eq!(
+        suite.balance(&skyway_escrow(), &suite.denom),
+        Uint128::new(PAID - FEE)
+    );
+    assert_eq!(
+        suite.balance(&suite.contract, &suite.denom),
+        Uint128::new(FEE)
+    );
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::zero());
+}
+
+#[test]
+fn bridge_rejects_a_fee_eating_the_funds() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::SetFeeSchedule {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                fee_schedule: Some(FeeSchedule {
+                    flat: Uint128::new(PAID),
+                    bps: 0,
+                    min: Uint128::zero(),
+                    max: None,
+                }),
+            },
+        )
+        .unwrap();
+    let user = suite.addr("user");
+    suite.mint(&user, PAID);
+    let funds = coins(PAID, &suite.denom);
+    let err = contract_error(suite.execute_with_funds(&user, bridge_msg(), &funds));
+    assert!(matches!(err, ContractError::FeeExceedsAmount { .. }));
+}
+
+#[test]
+fn cancel_pays_back_amount_and_fee() {
+    let (mut suite, user) = bridged();
+    let owner = suite.owner.clone();
+    let tx_id = suite.last_tx_id();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTx {
+                transaction_id: tx_id,
+                transfer_id: None,
+            },
+        )
+        .unwrap();
+    let transfer = transfer(&suite, 1);
+    assert_eq!(transfer.status, TransferStatus::Cancelled);
+    assert_eq!(transfer.refunded, Some(Uint128::new(PAID)));
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(PAID));
+    assert_eq!(
+        suite.balance(&suite.contract, &suite.denom),
+        Uint128::zero()
+    );
+    assert_eq!(fee_balances(&suite), vec![]);
+}
+
+#[test]
+fn cancel_pays_back_only_the_unclaimed_fee() {
+    let (mut suite, user) = bridged();
+    let owner = suite.owner.clone();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::ClaimFees {
+                asset_id: None,
+                recipient: None,
+            },
+        )
+        .unwrap();
+    let tx_id = suite.last_tx_id();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTx {
+                transaction_id: tx_id,
+                transfer_id: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(transfer(&suite, 1).refunded, Some(Uint128::new(PAID - FEE)));
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(PAID - FEE));
+}
+
+#[test]
+fn cancelled_transfer_cannot_be_confirmed() {
+    let (mut suite, user) = bridged();
+    let owner = suite.owner.clone();
+    let tx_id = suite.last_tx_id();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTx {
+                transaction_id: tx_id,
+                transfer_id: None,
+            },
+        )
+        .unwrap();
+    let err = contract_error(suite.sudo(SudoMsg::TransferConfirmed {
+        transaction_id: tx_id,
+    }));
+    assert!(matches!(
+        err,
+        ContractError::InvalidTransferStatus { id: 1, .. }
+    ));
+    assert_eq!(transfer(&suite, 1).status, TransferStatus::Cancelled);
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(PAID));
+}
+
+#[test]
+fn batched_transfer_cannot_be_cancelled() {
+    let (mut suite, user) = bridged();
+    let owner = suite.owner.clone();
+    let tx_id = suite.last_tx_id();
+    suite.batch(tx_id);
+    // Skyway refuses the cancel, which reverts the whole message
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTx {
+                transaction_id: tx_id,
+                transfer_id: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(transfer(&suite, 1).status, TransferStatus::Pending);
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::zero());
+
+    suite
+        .sudo(SudoMsg::TransferConfirmed {
+            transaction_id: tx_id,
+        })
+        .unwrap();
+    let transfer = transfer(&suite, 1);
+    assert_eq!(transfer.status, TransferStatus::Confirmed);
+    assert_eq!(transfer.refunded, None);
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::zero());
+}
+
+#[test]
+fn refund_pays_back_once() {
+    let (mut suite, user) = bridged();
+    let tx_id = suite.last_tx_id();
+    suite.batch(tx_id);
+    let returned = coin(PAID - FEE, &suite.denom);
+    suite.return_funds(returned);
+    suite
+        .sudo(SudoMsg::TransferRefunded {
+            transaction_id: tx_id,
+        })
+        .unwrap();
+    let refunded = transfer(&suite, 1);
+    assert_eq!(refunded.status, TransferStatus::Refunded);
+    assert_eq!(refunded.refunded, Some(Uint128::new(PAID)));
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(PAID));
+
+    let err = contract_error(suite.sudo(SudoMsg::TransferRefunded {
+        transaction_id: tx_id,
+    }));
+    assert!(matches!(
+        err,
+        ContractError::InvalidTransferStatus { id: 1, .. }
+    ));
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(PAID));
+}
+
+#[test]
+fn operator_transfer_is_not_paid_back() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let contract = suite.contract.clone();
+    suite.mint(&contract, PAID);
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::SendPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+                amount: Uint128::new(PAID),
+                nonce: Uint128::one(),
+            },
+        )
+        .unwrap();
+    assert_eq!(transfer(&suite, 1).origin, TransferOrigin::Operator);
+    let tx_id = suite.last_tx_id();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTx {
+                transaction_id: tx_id,
+                transfer_id: None,
+            },
+        )
+        .unwrap();
+    let transfer = transfer(&suite, 1);
+    assert_eq!(transfer.status, TransferStatus::Cancelled);
+    assert_eq!(transfer.refunded, None);
+    assert_eq!(suite.balance(&contract, &suite.denom), Uint128::new(PAID));
+    assert_eq!(suite.balance(&owner, &suite.denom), Uint128::zero());
+}
@@ -0,0 +1,195 @@
+use cosmwasm_std::{coin, coins, Event, Uint128};
+
+use super::{contract_error, Suite, CHAIN_ID, DESTINATION};
+use crate::msg::{ExecuteMsg, QueryMsg, SudoMsg};
+use crate::state::{JobResult, Transfer, TransferStatus};
+use crate::ContractError;
+
+const AMOUNT: u128 = 1_000;
+
+// Operator transfer of AMOUNT waiting in the Skyway pool
+fn sent() -> (Suite, u64) {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let contract = suite.contract.clone();
+    suite.mint(&contract, AMOUNT);
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::SendPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+                amount: Uint128::new(AMOUNT),
+                nonce: Uint128::one(),
+            },
+        )
+        .unwrap();
+    let tx_id = suite.last_tx_id();
+    (suite, tx_id)
+}
+
+fn status(suite: &Suite) -> TransferStatus {
+    let transfer: Transfer = suite.query(QueryMsg::GetTransfer { id: 1 });
+    transfer.status
+}
+
+fn confirmed(transaction_id: u64) -> SudoMsg {
+    SudoMsg::TransferConfirmed { transaction_id }
+}
+
+fn refunded(transaction_id: u64) -> SudoMsg {
+    SudoMsg::TransferRefunded { transaction_id }
+}
+
+fn job_result(suite: &Suite, job_id: &str) -> Option<JobResult> {
+    suite.query(QueryMsg::GetJobResult {
+        job_id: job_id.to_string(),
+    })
+}
+
+#[test]
+fn confirmed_transfer_is_final() {
+    let (mut suite, tx_id) = sent();
+    assert_eq!(status(&suite), TransferStatus::Pending);
+    suite.batch(tx_id);
+    let res = suite.sudo(confirmed(tx_id)).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "transfer_confirmed")
+            .add_attribute("transfer_id", "1")
+            .add_attribute("nonce", "1")
+    ));
+    assert_eq!(status(&suite), TransferStatus::Confirmed);
+    for msg in [confirmed(tx_id), refunded(tx_id)] {
+        let err = contract_error(suite.sudo(msg));
+        assert!(matches!(
+            err,
+            ContractError::InvalidTransferStatus { id: 1, status } if status == "confirmed"
+        ));
+    }
+}
+
+#[test]
+fn refunded_transfer_is_final() {
+    let (mut suite, tx_id) = sent();
+    suite.batch(tx_id);
+    let returned = coin(AMOUNT, &suite.denom);
+    suite.return_funds(returned);
+    suite.sudo(refunded(tx_id)).unwrap();
+    assert_eq!(status(&suite), TransferStatus::Refunded);
+    for msg in [confirmed(tx_id), refunded(tx_id)] {
+        let err = contract_error(suite.sudo(msg));
+        assert!(matches!(
+            err,
+            ContractError::InvalidTransferStatus { id: 1, status } if status == "refunded"
+        ));
+    }
+    // Operator funds stay in the contract
+    let contract = suite.contract.clone();
+    assert_eq!(suite.balance(&contract, &suite.denom), Uint128::new(AMOUNT));
+}
+
+#[test]
+fn unknown_transaction_is_rejected() {
+    let (mut suite, tx_id) = sent();
+    for msg in [confirmed(tx_id + 1), refunded(tx_id + 1)] {
+        let err = contract_error(suite.sudo(msg));
+        assert!(matches!(
+            err,
+            ContractError::UnknownTransaction { transaction_id } if transaction_id == tx_id + 1
+        ));
+    }
+    assert_eq!(status(&suite), TransferStatus::Pending);
+}
+
+#[test]
+fn refund_sends_the_funds_back_to_the_bridge_sender() {
+    let mut suite = Suite::new();
+    let user = suite.addr("user");
+    suite.mint(&user, AMOUNT);
+    let funds = coins(AMOUNT, &suite.denom);
+    suite
+        .execute_with_funds(
+            &user,
+            ExecuteMsg::Bridge {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+            },
+            &funds,
+        )
+        .unwrap();
+    let tx_id = suite.last_tx_id();
+    suite.batch(tx_id);
+    suite.return_funds(coin(AMOUNT, &suite.denom));
+
+    let res = suite.sudo(refunded(tx_id)).unwrap();
+    let contract = suite.contract.clone();
+    assert!(res.has_event(
+        &Event::new("transfer")
+            .add_attribute("recipient", user.as_str())
+            .add_attribute("sender", contract.as_str())
+            .add_attribute("amount", format!("{AMOUNT}{}", suite.denom))
+    ));
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "transfer_refunded")
+            .add_attribute("refunded", AMOUNT.to_string())
+    ));
+    assert_eq!(suite.balance(&user, &suite.denom), Uint128::new(AMOUNT));
+    assert_eq!(suite.balance(&contract, &suite.denom), Uint128::zero());
+}
+
+#[test]
+fn job_results_are_recorded() {
+    let mut suite = Suite::new();
+    assert_eq!(job_result(&suite, "job"), None);
+    suite
+        .sudo(SudoMsg::JobExecuted {
+            job_id: "job".to_string(),
+            chain_id: CHAIN_ID.to_string(),
+        })
+        .unwrap();
+    let block = suite.app.block_info();
+    assert_eq!(
+        job_result(&suite, "job"),
+        Some(JobResult {
+            chain_id: CHAIN_ID.to_string(),
+            success: true,
+            error: None,
+            height: block.height,
+            time: block.time,
+        })
+    );
+
+    // A later run replaces the result
+    suite.app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+    let res = suite
+        .sudo(SudoMsg::JobFailed {
+            job_id: "job".to_string(),
+            chain_id: CHAIN_ID.to_string(),
+            error: "out of gas".to_string(),
+        })
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "job_failed")
+            .add_attribute("error", "out of gas")
+    ));
+    let block = suite.app.block_info();
+    assert_eq!(
+        job_result(&suite, "job"),
+        Some(JobResult {
+            chain_id: CHAIN_ID.to_string(),
+            success: false,
+            error: Some("out of gas".to_string()),
+            height: block.height,
+            time: block.time,
+        })
+    );
+    assert_eq!(job_result(&suite, "other"), None);
+}
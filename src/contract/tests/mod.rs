@@ -0,0 +1,294 @@
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomMsg, CustomQuery, DepsMut, Empty,
+    Env, Event, MessageInfo, Querier, Response, Storage, Uint128,
+};
+use cw_multi_test::error::{bail, AnyResult};
+use cw_multi_test::{
+    App, AppBuilder, AppResponse, BankKeeper, BankSudo, ContractWrapper, CosmosRouter, Executor,
+    Module, WasmKeeper,
+};
+use cw_storage_plus::{Item, Map};
+use serde::de::DeserializeOwned;
+
+use crate::contract::reply::TX_ID_EVENT;
+use crate::contract::{execute, instantiate, query, reply, sudo};
+use crate::msg::{ChainRegistration, ExecuteMsg, InstantiateMsg, PalomaMsg, QueryMsg, SudoMsg};
+use crate::state::ChainKind;
+use crate::ContractError;
+
+mod assets;
+mod bridge;
+mod callbacks;
+mod nonces;
+mod ownership;
+mod replies;
+mod roles;
+mod withdrawals;
+
+pub const CHAIN_ID: &str = "eth-main";
+pub const DESTINATION: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+// Outgoing transactions still in the Skyway pool, with who sent them and what they escrow
+const POOL: Map<u64, (Addr, Coin)> = Map::new("test_skyway_pool");
+const LAST_TX_ID: Item<u64> = Item::new("test_skyway_last_tx_id");
+
+// Stand-in for the Paloma Skyway and Scheduler modules. A send_tx moves the coins to the
+// Skyway escrow and emits the transaction id; a cancel_tx returns them, and fails once the
+// transaction has left the pool for a batch.
+pub struct PalomaModule;
+
+pub type PalomaApp =
+    App<BankKeeper, MockApi, MockStorage, PalomaModule, WasmKeeper<PalomaMsg, Empty>>;
+
+pub fn skyway_escrow() -> Addr {
+    MockApi::default().addr_make("skyway")
+}
+
+impl Module for PalomaModule {
+    type ExecT = PalomaMsg;
+    type QueryT = Empty;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: PalomaMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            PalomaMsg::SkywayMsg {
+                send_tx: Some(send_tx),
+                cancel_tx: None,
+            } => {
+                let split = send_tx
+                    .amount
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(send_tx.amount.len());
+                let (amount, denom) = send_tx.amount.split_at(split);
+                let coin = Coin::new(amount.parse::<u128>()?, denom);
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender.clone(),
+                    BankMsg::Send {
+                        to_address: skyway_escrow().to_string(),
+                        amount: vec![coin.clone()],
+                    }
+                    .into(),
+                )?;
+                let tx_id = LAST_TX_ID.may_load(storage)?.unwrap_or(100) + 1;
+                LAST_TX_ID.save(storage, &tx_id)?;
+                POOL.save(storage, tx_id, &(sender, coin))?;
+                // Typed events carry JSON-encoded attribute values
+                Ok(AppResponse {
+                    events: vec![Event::new(TX_ID_EVENT)
+                        .add_attribute("message", "\"send_to_remote\"")
+                        .add_attribute("tx_id", format!("\"{tx_id}\""))],
+                    data: None,
+                })
+            }
+            PalomaMsg::SkywayMsg {
+                send_tx: None,
+                cancel_tx: Some(cancel_tx),
+            } => {
+                let (owner, coin) = match POOL.may_load(storage, cancel_tx.transaction_id)? {
+                    Some(entry) => entry,
+                    None => bail!(
+                        "transaction {} is not in the pool",
+                        cancel_tx.transaction_id
+                    ),
+                };
+                POOL.remove(storage, cancel_tx.transaction_id);
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    skyway_escrow(),
+                    BankMsg::Send {
+                        to_address: owner.to_string(),
+                        amount: vec![coin],
+                    }
+                    .into(),
+                )
+            }
+            PalomaMsg::SchedulerMsg { .. } => Ok(AppResponse::default()),
+            msg => bail!("unexpected Paloma message {msg:?}"),
+        }
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        _request: Empty,
+    ) -> AnyResult<Binary> {
+        bail!("no Paloma queries")
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("no Paloma sudo")
+    }
+}
+
+// The contract answers instantiate without custom messages
+fn instantiate_paloma(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response<PalomaMsg>, ContractError> {
+    Ok(instantiate(deps, env, info, msg)?
+        .change_custom()
+        .expect("no messages"))
+}
+
+pub fn contract_error(result: AnyResult<AppResponse>) -> ContractError {
+    result
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .expect("contract error")
+}
+
+pub struct Suite {
+    pub app: PalomaApp,
+    pub contract: Addr,
+    pub owner: Addr,
+    pub denom: String,
+}
+
+impl Suite {
+    // Contract with its default asset registered on CHAIN_ID
+    pub fn new() -> Self {
+        let mut app = AppBuilder::new_custom()
+            .with_custom(PalomaModule)
+            .build(|_, _, _| {});
+        let owner = app.api().addr_make("owner");
+        let manager = app.api().addr_make("manager");
+        let code_id = app.store_code(Box::new(
+            ContractWrapper::new(execute, instantiate_paloma, query)
+                .with_sudo_empty(sudo)
+                .with_reply(reply),
+        ));
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    pusd_manager: manager.clone(),
+                    pusd_denom: None,
+                },
+                &[],
+                "pusd-connector",
+                None,
+            )
+            .unwrap();
+        let mut suite = Suite {
+            app,
+            contract,
+            owner: owner.clone(),
+            denom: crate::state::default_pusd_denom(&manager),
+        };
+        suite
+            .execute(
+                &owner,
+                ExecuteMsg::RegisterChain {
+                    chain_id: CHAIN_ID.to_string(),
+                    asset_id: None,
+                    chain_setting: ChainRegistration {
+                        job_id: "job".to_string(),
+                        chain_kind: ChainKind::Evm,
+                    },
+                },
+            )
+            .unwrap();
+        suite
+    }
+
+    pub fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    pub fn execute(&mut self, sender: &Addr, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+        self.execute_with_funds(sender, msg, &[])
+    }
+
+    pub fn execute_with_funds(
+        &mut self,
+        sender: &Addr,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(sender.clone(), self.contract.clone(), &msg, funds)
+    }
+
+    pub fn sudo(&mut self, msg: SudoMsg) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(self.contract.clone(), &msg)
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: QueryMsg) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &msg)
+            .unwrap()
+    }
+
+    pub fn balance(&self, address: &Addr, denom: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+    }
+
+    pub fn mint(&mut self, address: &Addr, amount: u128) {
+        let denom = self.denom.clone();
+        self.app
+            .sudo(
+                BankSudo::Mint {
+                    to_address: address.to_string(),
+                    amount: coins(amount, denom),
+                }
+                .into(),
+            )
+            .unwrap();
+    }
+
+    // Skyway batches a pooled transaction: it can no longer be cancelled
+    pub fn batch(&mut self, tx_id: u64) {
+        POOL.remove(self.app.storage_mut(), tx_id);
+    }
+
+    // Skyway hands the escrowed coins of a batched transaction back to the contract
+    pub fn return_funds(&mut self, coin: Coin) {
+        self.app
+            .send_tokens(skyway_escrow(), self.contract.clone(), &[coin])
+            .unwrap();
+    }
+
+    pub fn last_tx_id(&self) -> u64 {
+        LAST_TX_ID.load(self.app.storage()).unwrap()
+    }
+}
@@ -0,0 +1,131 @@
+use cosmwasm_std::{coins, Uint128};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{AppResponse, BankSudo};
+
+use super::{contract_error, Suite, CHAIN_ID, DESTINATION};
+use crate::msg::{ChainRegistration, ExecuteMsg, QueryMsg};
+use crate::state::{default_pusd_denom, ChainKind, Transfer, TransferStatus};
+use crate::ContractError;
+
+const OTHER: &str = "other";
+
+// Suite with a second asset on CHAIN_ID and funds of both assets in the contract
+fn two_assets() -> Suite {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let manager = suite.addr("other_manager");
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::RegisterAsset {
+                asset_id: OTHER.to_string(),
+                manager: manager.to_string(),
+                denom: None,
+                decimals: 6,
+            },
+        )
+        .unwrap();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::RegisterChain {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: Some(OTHER.to_string()),
+                chain_setting: ChainRegistration {
+                    job_id: "job".to_string(),
+                    chain_kind: ChainKind::Evm,
+                },
+            },
+        )
+        .unwrap();
+    let contract = suite.contract.clone();
+    suite.mint(&contract, 1_000);
+    suite
+        .app
+        .sudo(
+            BankSudo::Mint {
+                to_address: contract.to_string(),
+                amount: coins(1_000, default_pusd_denom(&manager)),
+            }
+            .into(),
+        )
+        .unwrap();
+    suite
+}
+
+fn send(suite: &mut Suite, asset_id: Option<&str>, nonce: u128) -> AnyResult<AppResponse> {
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        ExecuteMsg::SendPusd {
+            chain_id: CHAIN_ID.to_string(),
+            asset_id: asset_id.map(str::to_string),
+            to: DESTINATION.to_string(),
+            amount: Uint128::new(100),
+            nonce: Uint128::new(nonce),
+        },
+    )
+}
+
+fn is_nonce_used(suite: &Suite, asset_id: Option<&str>, nonce: u128) -> bool {
+    suite.query(QueryMsg::IsNonceUsed {
+        chain_id: CHAIN_ID.to_string(),
+        asset_id: asset_id.map(str::to_string),
+        nonce: Uint128::new(nonce),
+    })
+}
+
+#[test]
+fn nonce_is_used_once_per_asset_and_chain() {
+    let mut suite = two_assets();
+    assert!(!is_nonce_used(&suite, None, 1));
+    send(&mut suite, None, 1).unwrap();
+    assert!(is_nonce_used(&suite, None, 1));
+    assert!(!is_nonce_used(&suite, None, 2));
+    assert!(!is_nonce_used(&suite, Some(OTHER), 1));
+
+    let err = contract_error(send(&mut suite, None, 1));
+    assert!(matches!(
+        err,
+        ContractError::NonceAlreadyUsed { asset_id, chain_id, nonce }
+            if asset_id == "pusd" && chain_id == CHAIN_ID && nonce.u128() == 1
+    ));
+
+    // Another asset has its own nonces
+    send(&mut suite, Some(OTHER), 1).unwrap();
+    assert!(is_nonce_used(&suite, Some(OTHER), 1));
+    let err = contract_error(send(&mut suite, Some(OTHER), 1));
+    assert!(matches!(err, ContractError::NonceAlreadyUsed { .. }));
+}
+
+#[test]
+fn cancel_transfer_looks_up_the_nonce_of_its_asset() {
+    let mut suite = two_assets();
+    let owner = suite.owner.clone();
+    send(&mut suite, None, 1).unwrap();
+    send(&mut suite, Some(OTHER), 1).unwrap();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::CancelTransfer {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: Some(OTHER.to_string()),
+                nonce: Uint128::one(),
+            },
+        )
+        .unwrap();
+    let transfer: Transfer = suite.query(QueryMsg::GetTransfer { id: 1 });
+    assert_eq!(transfer.status, TransferStatus::Pending);
+    let transfer: Transfer = suite.query(QueryMsg::GetTransfer { id: 2 });
+    assert_eq!(transfer.status, TransferStatus::Cancelled);
+
+    let err = contract_error(suite.execute(
+        &owner,
+        ExecuteMsg::CancelTransfer {
+            chain_id: CHAIN_ID.to_string(),
+            asset_id: None,
+            nonce: Uint128::new(2),
+        },
+    ));
+    assert!(matches!(err, ContractError::NonceNotFound { .. }));
+}
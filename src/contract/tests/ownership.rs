@@ -0,0 +1,165 @@
+use cosmwasm_std::Timestamp;
+
+use super::{contract_error, Suite};
+use crate::msg::{ExecuteMsg, OwnershipResponse, QueryMsg};
+use crate::ContractError;
+
+fn propose(suite: &mut Suite, new_owner: &str, expires_at: Option<Timestamp>) {
+    let owner = suite.owner.clone();
+    let new_owner = suite.addr(new_owner).to_string();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::ProposeOwner {
+                new_owner,
+                expires_at,
+            },
+        )
+        .unwrap();
+}
+
+fn ownership(suite: &Suite) -> OwnershipResponse {
+    suite.query(QueryMsg::GetOwnership {})
+}
+
+fn advance(suite: &mut Suite, seconds: u64) {
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(seconds));
+}
+
+#[test]
+fn accept_moves_ownership_to_the_proposed_owner() {
+    let mut suite = Suite::new();
+    let previous = suite.owner.clone();
+    let next = suite.addr("next");
+    propose(&mut suite, "next", None);
+    assert_eq!(ownership(&suite).owner, previous);
+    assert_eq!(ownership(&suite).pending_owner, Some(next.clone()));
+
+    suite
+        .execute(&next, ExecuteMsg::AcceptOwnership {})
+        .unwrap();
+    let ownership = ownership(&suite);
+    assert_eq!(ownership.owner, next);
+    assert_eq!(ownership.pending_owner, None);
+    assert_eq!(ownership.pending_expires_at, None);
+    // The previous owner keeps no rights
+    let err = contract_error(suite.execute(
+        &previous,
+        ExecuteMsg::ProposeOwner {
+            new_owner: previous.to_string(),
+            expires_at: None,
+        },
+    ));
+    assert!(matches!(err, ContractError::Unauthorized {}));
+}
+
+#[test]
+fn only_the_owner_proposes() {
+    let mut suite = Suite::new();
+    let stranger = suite.addr("stranger");
+    let err = contract_error(suite.execute(
+        &stranger,
+        ExecuteMsg::ProposeOwner {
+            new_owner: stranger.to_string(),
+            expires_at: None,
+        },
+    ));
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    assert_eq!(ownership(&suite).pending_owner, None);
+}
+
+#[test]
+fn only_the_pending_owner_accepts() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let stranger = suite.addr("stranger");
+    let err = contract_error(suite.execute(&stranger, ExecuteMsg::AcceptOwnership {}));
+    assert!(matches!(err, ContractError::NoPendingOwner {}));
+
+    propose(&mut suite, "next", None);
+    for sender in [&stranger, &owner] {
+        let err = contract_error(suite.execute(sender, ExecuteMsg::AcceptOwnership {}));
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+    assert_eq!(ownership(&suite).owner, owner);
+}
+
+#[test]
+fn a_new_proposal_replaces_the_pending_one() {
+    let mut suite = Suite::new();
+    let first = suite.addr("first");
+    let second = suite.addr("second");
+    propose(&mut suite, "first", None);
+    propose(&mut suite, "second", None);
+    let err = contract_error(suite.execute(&first, ExecuteMsg::AcceptOwnership {}));
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    suite
+        .execute(&second, ExecuteMsg::AcceptOwnership {})
+        .unwrap();
+    assert_eq!(ownership(&suite).owner, second);
+}
+
+#[test]
+fn expiry_must_be_in_the_future() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let now = suite.app.block_info().time;
+    for expires_at in [now, now.minus_seconds(1)] {
+        let err = contract_error(suite.execute(
+            &owner,
+            ExecuteMsg::ProposeOwner {
+                new_owner: owner.to_string(),
+                expires_at: Some(expires_at),
+            },
+        ));
+        assert!(matches!(err, ContractError::InvalidExpiry {}));
+    }
+}
+
+#[test]
+fn accept_before_expiry() {
+    let mut suite = Suite::new();
+    let next = suite.addr("next");
+    let expires_at = suite.app.block_info().time.plus_seconds(100);
+    propose(&mut suite, "next", Some(expires_at));
+    assert_eq!(ownership(&suite).pending_expires_at, Some(expires_at));
+    advance(&mut suite, 99);
+    suite
+        .execute(&next, ExecuteMsg::AcceptOwnership {})
+        .unwrap();
+    assert_eq!(ownership(&suite).owner, next);
+}
+
+#[test]
+fn accept_at_expiry_is_rejected() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let next = suite.addr("next");
+    let expires_at = suite.app.block_info().time.plus_seconds(100);
+    propose(&mut suite, "next", Some(expires_at));
+    advance(&mut suite, 100);
+    let err = contract_error(suite.execute(&next, ExecuteMsg::AcceptOwnership {}));
+    assert!(matches!(err, ContractError::OwnershipProposalExpired {}));
+    assert_eq!(ownership(&suite).owner, owner);
+}
+
+#[test]
+fn cancel_drops_the_proposal() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let next = suite.addr("next");
+    let err = contract_error(suite.execute(&owner, ExecuteMsg::CancelOwnershipProposal {}));
+    assert!(matches!(err, ContractError::NoPendingOwner {}));
+
+    propose(&mut suite, "next", None);
+    let err = contract_error(suite.execute(&next, ExecuteMsg::CancelOwnershipProposal {}));
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    suite
+        .execute(&owner, ExecuteMsg::CancelOwnershipProposal {})
+        .unwrap();
+    assert_eq!(ownership(&suite).pending_owner, None);
+    let err = contract_error(suite.execute(&next, ExecuteMsg::AcceptOwnership {}));
+    assert!(matches!(err, ContractError::NoPendingOwner {}));
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::testing::mock_dependencies;
+use cosmwasm_std::{to_json_binary, Event, Reply, SubMsgResponse, SubMsgResult, Uint128};
+
+use super::{Suite, CHAIN_ID, DESTINATION};
+use crate::contract::reply::{self, transaction_id, TX_ID_EVENT, TX_ID_MISSING_EVENT};
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::Transfer;
+
+#[allow(deprecated)]
+fn response(events: Vec<Event>) -> SubMsgResponse {
+    SubMsgResponse {
+        events,
+        data: None,
+        msg_responses: vec![],
+    }
+}
+
+// Event as emitted by Skyway for a send_tx
+fn tx_id_event(tx_id: &str) -> Event {
+    Event::new(TX_ID_EVENT)
+        .add_attribute("message", "\"send_to_remote\"")
+        .add_attribute("tx_id", tx_id)
+}
+
+#[test]
+fn transaction_id_reads_the_skyway_event() {
+    let events = vec![
+        Event::new("message").add_attribute("tx_id", "\"7\""),
+        tx_id_event("\"42\""),
+    ];
+    assert_eq!(transaction_id(&response(events)), Some(42));
+    assert_eq!(transaction_id(&response(vec![tx_id_event("42")])), Some(42));
+}
+
+#[test]
+fn transaction_id_ignores_anything_else() {
+    let cases = [
+        vec![],
+        vec![Event::new("message").add_attribute("tx_id", "\"7\"")],
+        vec![Event::new(TX_ID_EVENT).add_attribute("outgoing_tx_id", "\"7\"")],
+        vec![tx_id_event("\"seven\"")],
+    ];
+    for events in cases {
+        assert_eq!(
+            transaction_id(&response(events.clone())),
+            None,
+            "{events:?}"
+        );
+    }
+    #[allow(deprecated)]
+    let with_data = SubMsgResponse {
+        data: Some(to_json_binary(&7u64).unwrap()),
+        ..response(vec![])
+    };
+    assert_eq!(transaction_id(&with_data), None);
+}
+
+#[test]
+fn reply_without_transaction_id_flags_the_transfer() {
+    let mut deps = mock_dependencies();
+    let msg = Reply {
+        id: 0,
+        payload: to_json_binary(&3u64).unwrap(),
+        gas_used: 0,
+        result: SubMsgResult::Ok(response(vec![])),
+    };
+    let res = reply::send_tx(deps.as_mut(), msg).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new(TX_ID_MISSING_EVENT).add_attribute("transfer_id", "3")]
+    );
+}
+
+#[test]
+fn reply_records_the_transaction_id() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let contract = suite.contract.clone();
+    suite.mint(&contract, 100);
+    let res = suite
+        .execute(
+            &owner,
+            ExecuteMsg::SendPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+                amount: Uint128::new(100),
+                nonce: Uint128::one(),
+            },
+        )
+        .unwrap();
+    let tx_id = suite.last_tx_id();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("transaction_id", tx_id.to_string())));
+    assert!(!res
+        .events
+        .iter()
+        .any(|event| event.ty.ends_with(TX_ID_MISSING_EVENT)));
+    let transfer: Transfer = suite.query(QueryMsg::GetTransfer { id: 1 });
+    assert_eq!(transfer.skyway_tx_id, Some(tx_id));
+}
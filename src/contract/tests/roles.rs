@@ -0,0 +1,427 @@
+use cosmwasm_std::{Addr, Uint128, Uint256};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::AppResponse;
+
+use super::{contract_error, Suite, CHAIN_ID, DESTINATION};
+use crate::msg::{ChainRegistration, ExecuteMsg, QueryMsg};
+use crate::state::{ChainKind, Role};
+use crate::ContractError;
+
+const ROLES: [Role; 6] = [
+    Role::Admin,
+    Role::Operator,
+    Role::FeeManager,
+    Role::Guardian,
+    Role::FeeCollector,
+    Role::Compliance,
+];
+
+fn is_unauthorized(result: AnyResult<AppResponse>) -> bool {
+    result.err().is_some_and(|err| {
+        matches!(
+            err.downcast::<ContractError>(),
+            Ok(ContractError::Unauthorized {})
+        )
+    })
+}
+
+// One sample of every role-gated message with the role it requires
+fn gated_messages(suite: &Suite) -> Vec<(ExecuteMsg, Role)> {
+    let someone = suite.addr("someone").to_string();
+    vec![
+        (
+            ExecuteMsg::RegisterChain {
+                chain_id: "other".to_string(),
+                asset_id: None,
+                chain_setting: ChainRegistration {
+                    job_id: "job".to_string(),
+                    chain_kind: ChainKind::Evm,
+                },
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::DeregisterChain {
+                chain_id: "missing".to_string(),
+                asset_id: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SetChainEnabled {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                enabled: true,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::RegisterAsset {
+                asset_id: "other".to_string(),
+                manager: someone.clone(),
+                denom: None,
+                decimals: 6,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::ChangeConfig {
+                default_asset: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SetPaloma {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::UpdateWithdrawLimit {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_withdraw_limit: Uint256::from(100u32),
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::UpdatePusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_pusd: DESTINATION.to_string(),
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::UpdatePusdManager {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_pusd_manager: DESTINATION.to_string(),
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::GrantRole {
+                role: Role::Guardian,
+                address: someone.clone(),
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::RevokeRole {
+                role: Role::Guardian,
+                address: someone.clone(),
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::ExecuteRemote {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                function_name: "ping".to_string(),
+                args: vec![],
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SetRateLimit {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                rate_limit: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SetDefaultUserQuota {
+                asset_id: None,
+                quota: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SetUserQuota {
+                address: someone.clone(),
+                asset_id: None,
+                quota: None,
+            },
+            Role::Admin,
+        ),
+        (
+            ExecuteMsg::SendPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                to: DESTINATION.to_string(),
+                amount: Uint128::new(100),
+                nonce: Uint128::one(),
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::WithdrawPusd {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                recipient: DESTINATION.to_string(),
+                amount: Uint128::new(100),
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::ReWithdrawPusd {
+                nonce: 1,
+                asset_id: None,
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::CancelWithdrawPusd {
+                nonce: 1,
+                asset_id: None,
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::CancelTx {
+                transaction_id: 1,
+                transfer_id: None,
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::CancelTransfer {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                nonce: Uint128::one(),
+            },
+            Role::Operator,
+        ),
+        (
+            ExecuteMsg::UpdateRefundWallet {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_refund_wallet: DESTINATION.to_string(),
+            },
+            Role::FeeManager,
+        ),
+        (
+            ExecuteMsg::UpdateGasFee {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_gas_fee: Uint256::from(1u32),
+            },
+            Role::FeeManager,
+        ),
+        (
+            ExecuteMsg::UpdateServiceFeeCollector {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_service_fee_collector: DESTINATION.to_string(),
+            },
+            Role::FeeManager,
+        ),
+        (
+            ExecuteMsg::UpdateServiceFee {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                new_service_fee: Uint256::from(1u32),
+            },
+            Role::FeeManager,
+        ),
+        (
+            ExecuteMsg::SetFeeSchedule {
+                chain_id: CHAIN_ID.to_string(),
+                asset_id: None,
+                fee_schedule: None,
+            },
+            Role::FeeManager,
+        ),
+        (
+            ExecuteMsg::ClaimFees {
+                asset_id: None,
+                recipient: None,
+            },
+            Role::FeeCollector,
+        ),
+        (
+            ExecuteMsg::SetSenderDenied {
+                address: someone,
+                denied: true,
+            },
+            Role::Compliance,
+        ),
+        (
+            ExecuteMsg::SetDestinationDenied {
+                chain_id: CHAIN_ID.to_string(),
+                address: DESTINATION.to_string(),
+                denied: true,
+            },
+            Role::Compliance,
+        ),
+        (
+            ExecuteMsg::SetDestinationAllowed {
+                chain_id: CHAIN_ID.to_string(),
+                address: DESTINATION.to_string(),
+                allowed: true,
+            },
+            Role::Compliance,
+        ),
+        (
+            ExecuteMsg::SetAllowlistMode {
+                chain_id: CHAIN_ID.to_string(),
+                enabled: true,
+            },
+            Role::Compliance,
+        ),
+        (
+            ExecuteMsg::Pause {
+                chain_id: Some(CHAIN_ID.to_string()),
+            },
+            Role::Guardian,
+        ),
+        (
+            ExecuteMsg::Unpause {
+                chain_id: Some(CHAIN_ID.to_string()),
+            },
+            Role::Guardian,
+        ),
+    ]
+}
+
+fn grant(suite: &mut Suite, role: Role, address: &Addr) {
+    let owner = suite.owner.clone();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::GrantRole {
+                role,
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+}
+
+fn has_role(suite: &Suite, role: Role, address: &Addr) -> bool {
+    suite.query(QueryMsg::HasRole {
+        role,
+        address: address.to_string(),
+    })
+}
+
+#[test]
+fn each_message_requires_its_role() {
+    let mut suite = Suite::new();
+    let holders: Vec<(Role, Addr)> = ROLES
+        .iter()
+        .map(|role| (*role, suite.addr(role.as_str())))
+        .collect();
+    for (role, holder) in &holders {
+        grant(&mut suite, *role, holder);
+    }
+    for (msg, required) in gated_messages(&suite) {
+        assert_eq!(msg.required_role(), Some(required), "{msg:?}");
+        for (role, holder) in &holders {
+            let unauthorized = is_unauthorized(suite.execute(holder, msg.clone()));
+            assert_eq!(
+                unauthorized,
+                *role != required,
+                "{role} calling {msg:?} requiring {required}"
+            );
+        }
+        let stranger = suite.addr("stranger");
+        assert!(is_unauthorized(suite.execute(&stranger, msg.clone())));
+    }
+}
+
+#[test]
+fn owner_holds_every_role() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    for role in ROLES {
+        assert!(has_role(&suite, role, &owner));
+    }
+    for (msg, _) in gated_messages(&suite) {
+        assert!(
+            !is_unauthorized(suite.execute(&owner, msg.clone())),
+            "{msg:?}"
+        );
+    }
+}
+
+#[test]
+fn role_less_messages_are_open() {
+    let msgs = [
+        ExecuteMsg::ProposeOwner {
+            new_owner: "next".to_string(),
+            expires_at: None,
+        },
+        ExecuteMsg::AcceptOwnership {},
+        ExecuteMsg::CancelOwnershipProposal {},
+        ExecuteMsg::RenounceRole { role: Role::Admin },
+        ExecuteMsg::Bridge {
+            chain_id: CHAIN_ID.to_string(),
+            asset_id: None,
+            to: DESTINATION.to_string(),
+        },
+    ];
+    for msg in msgs {
+        assert_eq!(msg.required_role(), None, "{msg:?}");
+    }
+}
+
+#[test]
+fn admin_grants_and_revokes_roles() {
+    let mut suite = Suite::new();
+    let admin = suite.addr("admin");
+    let guardian = suite.addr("guardian");
+    grant(&mut suite, Role::Admin, &admin);
+    let pause = ExecuteMsg::Pause { chain_id: None };
+    assert!(is_unauthorized(suite.execute(&guardian, pause.clone())));
+
+    suite
+        .execute(
+            &admin,
+            ExecuteMsg::GrantRole {
+                role: Role::Guardian,
+                address: guardian.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(has_role(&suite, Role::Guardian, &guardian));
+    assert!(!has_role(&suite, Role::Operator, &guardian));
+    let members: Vec<Addr> = suite.query(QueryMsg::GetRoleMembers {
+        role: Role::Guardian,
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(members, vec![guardian.clone()]);
+    suite.execute(&guardian, pause.clone()).unwrap();
+
+    let revoke = ExecuteMsg::RevokeRole {
+        role: Role::Guardian,
+        address: guardian.to_string(),
+    };
+    suite.execute(&admin, revoke.clone()).unwrap();
+    assert!(!has_role(&suite, Role::Guardian, &guardian));
+    assert!(is_unauthorized(suite.execute(&guardian, pause)));
+    let err = contract_error(suite.execute(&admin, revoke));
+    assert!(matches!(err, ContractError::RoleNotGranted { .. }));
+}
+
+#[test]
+fn holder_renounces_a_role() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let operator = suite.addr("operator");
+    grant(&mut suite, Role::Operator, &operator);
+    let renounce = ExecuteMsg::RenounceRole {
+        role: Role::Operator,
+    };
+    suite.execute(&operator, renounce.clone()).unwrap();
+    assert!(!has_role(&suite, Role::Operator, &operator));
+    let err = contract_error(suite.execute(&operator, renounce.clone()));
+    assert!(matches!(err, ContractError::RoleNotGranted { .. }));
+    // The owner holds roles implicitly and has nothing to renounce
+    let err = contract_error(suite.execute(&owner, renounce));
+    assert!(matches!(err, ContractError::RoleNotGranted { .. }));
+    assert!(has_role(&suite, Role::Operator, &owner));
+}
@@ -0,0 +1,126 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{AppResponse, ContractWrapper, Executor};
+
+use super::{contract_error, Suite};
+use crate::msg::{ExecuteMsg, ExternalExecuteMsg, PalomaMsg};
+use crate::state::Role;
+use crate::ContractError;
+
+const ASSET: &str = "managed";
+
+fn manager_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<PalomaMsg>> {
+    Ok(Response::new())
+}
+
+fn manager_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: ExternalExecuteMsg,
+) -> StdResult<Response<PalomaMsg>> {
+    Ok(Response::new())
+}
+
+fn manager_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
+
+// Suite with an asset handled by a manager contract accepting every withdraw action
+fn managed() -> Suite {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let code_id = suite.app.store_code(Box::new(ContractWrapper::new(
+        manager_execute,
+        manager_instantiate,
+        manager_query,
+    )));
+    let manager = suite
+        .app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "manager", None)
+        .unwrap();
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::RegisterAsset {
+                asset_id: ASSET.to_string(),
+                manager: manager.to_string(),
+                denom: None,
+                decimals: 6,
+            },
+        )
+        .unwrap();
+    suite
+}
+
+fn re_withdraw(suite: &mut Suite, sender: &Addr) -> AnyResult<AppResponse> {
+    suite.execute(
+        sender,
+        ExecuteMsg::ReWithdrawPusd {
+            nonce: 1,
+            asset_id: Some(ASSET.to_string()),
+        },
+    )
+}
+
+fn cancel_withdraw(suite: &mut Suite, sender: &Addr) -> AnyResult<AppResponse> {
+    suite.execute(
+        sender,
+        ExecuteMsg::CancelWithdrawPusd {
+            nonce: 1,
+            asset_id: Some(ASSET.to_string()),
+        },
+    )
+}
+
+fn has_action(res: &AppResponse, action: &str) -> bool {
+    res.has_event(&Event::new("wasm").add_attribute("action", action))
+}
+
+#[test]
+fn re_withdraw_is_halted_by_the_global_pause() {
+    let mut suite = managed();
+    let owner = suite.owner.clone();
+    suite
+        .execute(&owner, ExecuteMsg::Pause { chain_id: None })
+        .unwrap();
+    let err = contract_error(re_withdraw(&mut suite, &owner));
+    assert!(matches!(err, ContractError::PausedGlobally {}));
+    let res = cancel_withdraw(&mut suite, &owner).unwrap();
+    assert!(has_action(&res, "cancel_withdraw_pusd"));
+
+    suite
+        .execute(&owner, ExecuteMsg::Unpause { chain_id: None })
+        .unwrap();
+    let res = re_withdraw(&mut suite, &owner).unwrap();
+    assert!(has_action(&res, "re_withdraw_pusd"));
+}
+
+#[test]
+fn re_withdraw_rejects_a_denied_sender() {
+    let mut suite = managed();
+    let owner = suite.owner.clone();
+    let operator = suite.addr("operator");
+    for msg in [
+        ExecuteMsg::GrantRole {
+            role: Role::Operator,
+            address: operator.to_string(),
+        },
+        ExecuteMsg::SetSenderDenied {
+            address: operator.to_string(),
+            denied: true,
+        },
+    ] {
+        suite.execute(&owner, msg).unwrap();
+    }
+    let err = contract_error(re_withdraw(&mut suite, &operator));
+    assert!(matches!(err, ContractError::SenderDenied { address } if address == operator.as_str()));
+    cancel_withdraw(&mut suite, &operator).unwrap();
+}
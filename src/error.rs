@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +8,135 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+
+    #[error("No pending owner")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal expired")]
+    OwnershipProposalExpired {},
+
+    #[error("Expiry must be in the future")]
+    InvalidExpiry {},
+
+    #[error("Role {role} not granted to {address}")]
+    RoleNotGranted { role: String, address: String },
+
+    #[error("Paused: {chain_id}")]
+    Paused { chain_id: String },
+
+    #[error("Paused")]
+    PausedGlobally {},
+
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Expected a single coin of {denom} in funds")]
+    InvalidFunds { denom: String },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Asset decimals {decimals} exceed the bridge decimals")]
+    InvalidDecimals { decimals: u8 },
+
+    #[error("Amount {amount} with {decimals} decimals is out of the bridge range")]
+    AmountOutOfRange { amount: Uint128, decimals: u8 },
+
+    #[error("Unknown chain: {chain_id}")]
+    UnknownChain { chain_id: String },
+
+    #[error("Chain disabled: {chain_id}")]
+    ChainDisabled { chain_id: String },
+
+    #[error("Nonce {nonce} already used for {asset_id} on {chain_id}")]
+    NonceAlreadyUsed {
+        asset_id: String,
+        chain_id: String,
+        nonce: Uint128,
+    },
+
+    #[error("Transfer not found: {id}")]
+    TransferNotFound { id: u64 },
+
+    #[error("Transfer {id} is {status}")]
+    InvalidTransferStatus { id: u64, status: String },
+
+    #[error("No transfer of {asset_id} for nonce {nonce} on {chain_id}")]
+    NonceNotFound {
+        asset_id: String,
+        chain_id: String,
+        nonce: Uint128,
+    },
+
+    #[error("Skyway transaction id of transfer {id} is unknown")]
+    TransactionIdUnknown { id: u64 },
+
+    #[error("Transfer {id} does not belong to Skyway transaction {transaction_id}")]
+    TransactionMismatch { id: u64, transaction_id: u64 },
+
+    #[error("Unknown Skyway transaction: {transaction_id}")]
+    UnknownTransaction { transaction_id: u64 },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReply { id: u64 },
+
+    #[error("Invalid {kind} address: {value}")]
+    InvalidAddress { kind: String, value: String },
+
+    #[error("Invalid EVM address: {value}")]
+    InvalidEvmAddress { value: String },
+
+    #[error("Failed to ABI-encode {function}")]
+    AbiEncoding { function: String },
+
+    #[error("Unknown remote function: {name}")]
+    UnknownRemoteFunction { name: String },
+
+    #[error("{name} must be called through its own message")]
+    ReservedRemoteFunction { name: String },
+
+    #[error("Invalid function name: {name}")]
+    InvalidFunctionName { name: String },
+
+    #[error("Invalid ABI argument: {reason}")]
+    InvalidAbiArgument { reason: String },
+
+    #[error("Unknown asset {asset_id}")]
+    UnknownAsset { asset_id: String },
+
+    #[error("Asset {asset_id} is in use: {reason}")]
+    AssetInUse { asset_id: String, reason: String },
+
+    #[error("Invalid fee schedule: {reason}")]
+    InvalidFeeSchedule { reason: String },
+
+    #[error("Fee {fee} leaves nothing to bridge out of {amount}")]
+    FeeExceedsAmount { amount: Uint128, fee: Uint128 },
+
+    #[error("No fees to claim in {denom}")]
+    NoFeesToClaim { denom: String },
+
+    #[error("Invalid rate limit: {reason}")]
+    InvalidRateLimit { reason: String },
+
+    #[error("Rate limit of {chain_id} exceeded: {remaining} left in the window")]
+    RateLimitExceeded {
+        chain_id: String,
+        remaining: Uint128,
+    },
+
+    #[error("Transfer to {chain_id} above the per-transfer maximum of {max}")]
+    TransferTooLarge { chain_id: String, max: Uint128 },
+
+    #[error("Daily quota of {address} exceeded: {remaining} left today")]
+    UserQuotaExceeded { address: String, remaining: Uint128 },
+
+    #[error("Sender {address} is denied")]
+    SenderDenied { address: String },
+
+    #[error("Destination {address} on {chain_id} is denied")]
+    DestinationDenied { chain_id: String, address: String },
+
+    #[error("Destination {address} on {chain_id} is not allowlisted")]
+    DestinationNotAllowed { chain_id: String, address: String },
 }
@@ -1,6 +1,9 @@
+pub mod abi;
+pub mod address;
 pub mod contract;
 mod error;
 pub mod msg;
+pub mod skyway;
 pub mod state;
 
 pub use crate::error::ContractError;
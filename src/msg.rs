@@ -1,80 +1,332 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, CustomMsg, Uint128, Uint256};
+use cosmwasm_std::{Addr, Binary, Coin, CustomMsg, HexBinary, Timestamp, Uint128, Uint256};
 
 #[allow(unused_imports)]
-use crate::state::{ChainSetting, State};
+use crate::state::{
+    Asset, ChainKind, ChainSetting, FeeSchedule, JobResult, RateLimit, RemoteConfig, Role, State,
+    Transfer, TransferStatus, WithdrawAction,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
+    // Registered as the default asset
     pub pusd_manager: Addr,
+    // Defaults to factory/<pusd_manager>/upusd
+    pub pusd_denom: Option<String>,
 }
 
 #[cw_serde]
 pub struct MigrateMsg {}
 
+// Caller-supplied part of a ChainSetting; the remote config is only written by the
+// update_* handlers
 #[cw_serde]
+pub struct ChainRegistration {
+    pub job_id: String,
+    #[serde(default)]
+    pub chain_kind: ChainKind,
+}
+
+// Messages targeting a chain take an optional asset_id, defaulting to State.default_asset
+#[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     // Register Jobs in hash map with chain_id as key and job_id as value
     RegisterChain {
         chain_id: String,
-        chain_setting: ChainSetting,
+        asset_id: Option<String>,
+        chain_setting: ChainRegistration,
+    },
+    // Remove a chain and its job id
+    DeregisterChain {
+        chain_id: String,
+        asset_id: Option<String>,
+    },
+    // Enable or disable all outbound flows to a chain
+    SetChainEnabled {
+        chain_id: String,
+        asset_id: Option<String>,
+        enabled: bool,
     },
     SendPusd {
         chain_id: String,
+        asset_id: Option<String>,
         to: String,
         amount: Uint128,
         nonce: Uint128,
     },
+    // Bridge the asset sent in funds to `to` on a chain. Open to anyone.
+    Bridge {
+        chain_id: String,
+        asset_id: Option<String>,
+        to: String,
+    },
     WithdrawPusd {
         chain_id: String,
+        asset_id: Option<String>,
         recipient: String,
         amount: Uint128,
     },
+    // Retry a stuck withdrawal on the asset manager
+    ReWithdrawPusd {
+        nonce: u64,
+        asset_id: Option<String>,
+    },
+    // Cancel a withdrawal on the asset manager
+    CancelWithdrawPusd {
+        nonce: u64,
+        asset_id: Option<String>,
+    },
+    // Cancel a pooled Skyway transaction, marking the linked transfer as cancelled and paying
+    // back its Bridge sender
     CancelTx {
         transaction_id: u64,
+        transfer_id: Option<u64>,
+    },
+    // Cancel the Skyway transaction of the transfer sent with this nonce
+    CancelTransfer {
+        chain_id: String,
+        asset_id: Option<String>,
+        nonce: Uint128,
+    },
+    // Register an asset, or update the denom, manager and decimals of a registered one
+    RegisterAsset {
+        asset_id: String,
+        manager: String,
+        // Defaults to factory/<manager>/upusd
+        denom: Option<String>,
+        decimals: u8,
     },
     ChangeConfig {
-        owner: Option<Addr>,
-        pusd_manager: Option<Addr>,
+        default_asset: Option<String>,
+    },
+    // Propose a new owner. The owner changes only once the new owner accepts.
+    ProposeOwner {
+        new_owner: String,
+        expires_at: Option<Timestamp>,
+    },
+    // Accept a pending ownership proposal. Callable by the proposed owner only.
+    AcceptOwnership {},
+    // Drop the pending ownership proposal
+    CancelOwnershipProposal {},
+    // Grant a role to an address
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    // Revoke a role from an address
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    // Give up a role held by the sender
+    RenounceRole {
+        role: Role,
     },
     UpdateWithdrawLimit {
         chain_id: String,
+        asset_id: Option<String>,
         new_withdraw_limit: Uint256,
     },
     // Set Paloma address of a chain
     SetPaloma {
         chain_id: String,
+        asset_id: Option<String>,
     },
     // Update Refund Wallet
     UpdateRefundWallet {
         chain_id: String,
+        asset_id: Option<String>,
         new_refund_wallet: String,
     },
     // Update Gas Fee
     UpdateGasFee {
         chain_id: String,
+        asset_id: Option<String>,
         new_gas_fee: Uint256,
     },
     // Update Service Fee Collector
     UpdateServiceFeeCollector {
         chain_id: String,
+        asset_id: Option<String>,
         new_service_fee_collector: String,
     },
     // Update Service Fee
     UpdateServiceFee {
         chain_id: String,
+        asset_id: Option<String>,
         new_service_fee: Uint256,
     },
     // Update Pusd
     UpdatePusd {
         chain_id: String,
+        asset_id: Option<String>,
         new_pusd: String,
     },
     // Update Pusd Manager
     UpdatePusdManager {
         chain_id: String,
+        asset_id: Option<String>,
         new_pusd_manager: String,
     },
+    // Halt outbound flows globally, or for a single chain when chain_id is set
+    Pause {
+        chain_id: Option<String>,
+    },
+    // Resume outbound flows globally, or for a single chain when chain_id is set
+    Unpause {
+        chain_id: Option<String>,
+    },
+    // Call an arbitrary function on the remote connector of a chain
+    ExecuteRemote {
+        chain_id: String,
+        asset_id: Option<String>,
+        function_name: String,
+        args: Vec<AbiArg>,
+    },
+    // Set the fee charged on Bridge sends to a chain, or remove it when fee_schedule is None
+    SetFeeSchedule {
+        chain_id: String,
+        asset_id: Option<String>,
+        fee_schedule: Option<FeeSchedule>,
+    },
+    // Set the outbound rate limit of a chain, or remove it when rate_limit is None
+    SetRateLimit {
+        chain_id: String,
+        asset_id: Option<String>,
+        rate_limit: Option<RateLimit>,
+    },
+    // Set the daily quota of every sender of an asset, or remove it when quota is None
+    SetDefaultUserQuota {
+        asset_id: Option<String>,
+        quota: Option<Uint128>,
+    },
+    // Override the daily quota of one sender, or drop the override when quota is None
+    SetUserQuota {
+        address: String,
+        asset_id: Option<String>,
+        quota: Option<Uint128>,
+    },
+    // Add a Cosmos sender to the denylist, or remove it when denied is false
+    SetSenderDenied {
+        address: String,
+        denied: bool,
+    },
+    // Add a remote destination of a chain to the denylist, or remove it when denied is false
+    SetDestinationDenied {
+        chain_id: String,
+        address: String,
+        denied: bool,
+    },
+    // Add a remote destination of a chain to the allowlist, or remove it when allowed is false
+    SetDestinationAllowed {
+        chain_id: String,
+        address: String,
+        allowed: bool,
+    },
+    // Only accept allowlisted destinations on a chain while enabled
+    SetAllowlistMode {
+        chain_id: String,
+        enabled: bool,
+    },
+    // Send the collected fees of an asset to recipient, or to the sender when unset
+    ClaimFees {
+        asset_id: Option<String>,
+        recipient: Option<String>,
+    },
+}
+
+// Typed argument of a remote function call
+#[cw_serde]
+pub enum AbiArg {
+    Address(String),
+    Uint256(Uint256),
+    Bool(bool),
+    Bytes(Binary),
+    String(String),
+    // Every item must be of `kind`, which also types an empty array
+    Array { kind: AbiType, items: Vec<AbiArg> },
+}
+
+// ABI type of the items of an AbiArg::Array
+#[cw_serde]
+pub enum AbiType {
+    Address,
+    Uint256,
+    Bool,
+    Bytes,
+    String,
+    Array(Box<AbiType>),
+}
+
+impl ExecuteMsg {
+    // Role required to call the message. `None` means the message is open to anyone or the
+    // handler checks the sender itself.
+    pub fn required_role(&self) -> Option<Role> {
+        match self {
+            ExecuteMsg::RegisterChain { .. }
+            | ExecuteMsg::DeregisterChain { .. }
+            | ExecuteMsg::SetChainEnabled { .. }
+            | ExecuteMsg::RegisterAsset { .. }
+            | ExecuteMsg::ChangeConfig { .. }
+            | ExecuteMsg::SetPaloma { .. }
+            | ExecuteMsg::UpdateWithdrawLimit { .. }
+            | ExecuteMsg::UpdatePusd { .. }
+            | ExecuteMsg::UpdatePusdManager { .. }
+            | ExecuteMsg::GrantRole { .. }
+            | ExecuteMsg::RevokeRole { .. }
+            | ExecuteMsg::ExecuteRemote { .. }
+            | ExecuteMsg::SetRateLimit { .. }
+            | ExecuteMsg::SetDefaultUserQuota { .. }
+            | ExecuteMsg::SetUserQuota { .. } => Some(Role::Admin),
+            ExecuteMsg::SendPusd { .. }
+            | ExecuteMsg::WithdrawPusd { .. }
+            | ExecuteMsg::ReWithdrawPusd { .. }
+            | ExecuteMsg::CancelWithdrawPusd { .. }
+            | ExecuteMsg::CancelTx { .. }
+            | ExecuteMsg::CancelTransfer { .. } => Some(Role::Operator),
+            ExecuteMsg::UpdateRefundWallet { .. }
+            | ExecuteMsg::UpdateGasFee { .. }
+            | ExecuteMsg::UpdateServiceFeeCollector { .. }
+            | ExecuteMsg::UpdateServiceFee { .. }
+            | ExecuteMsg::SetFeeSchedule { .. } => Some(Role::FeeManager),
+            ExecuteMsg::ClaimFees { .. } => Some(Role::FeeCollector),
+            ExecuteMsg::SetSenderDenied { .. }
+            | ExecuteMsg::SetDestinationDenied { .. }
+            | ExecuteMsg::SetDestinationAllowed { .. }
+            | ExecuteMsg::SetAllowlistMode { .. } => Some(Role::Compliance),
+            ExecuteMsg::Pause { .. } | ExecuteMsg::Unpause { .. } => Some(Role::Guardian),
+            ExecuteMsg::ProposeOwner { .. }
+            | ExecuteMsg::AcceptOwnership {}
+            | ExecuteMsg::CancelOwnershipProposal {}
+            | ExecuteMsg::RenounceRole { .. }
+            | ExecuteMsg::Bridge { .. } => None,
+        }
+    }
+}
+
+// Callbacks from the Paloma bridge
+#[cw_serde]
+pub enum SudoMsg {
+    // Skyway batch carrying the transaction was relayed to the remote chain
+    TransferConfirmed {
+        transaction_id: u64,
+    },
+    // Skyway could not relay the transaction and returned the funds
+    TransferRefunded {
+        transaction_id: u64,
+    },
+    // Scheduler job ran on the remote chain
+    JobExecuted {
+        job_id: String,
+        chain_id: String,
+    },
+    // Scheduler job failed on the remote chain
+    JobFailed {
+        job_id: String,
+        chain_id: String,
+        error: String,
+    },
 }
 
 #[cw_serde]
@@ -93,8 +345,119 @@ pub enum QueryMsg {
     #[returns(State)]
     GetState {},
 
+    #[returns(AssetInfo)]
+    GetAsset { asset_id: String },
+
+    #[returns(Vec<AssetInfo>)]
+    GetAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(ChainSettingInfo)]
+    GetChainSetting {
+        chain_id: String,
+        asset_id: Option<String>,
+    },
+
     #[returns(Vec<ChainSettingInfo>)]
-    GetChainSettings {},
+    GetChainSettings {
+        asset_id: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(OwnershipResponse)]
+    GetOwnership {},
+
+    #[returns(bool)]
+    HasRole { role: Role, address: String },
+
+    #[returns(Vec<Addr>)]
+    GetRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PauseStatusResponse)]
+    GetPauseStatus {},
+
+    #[returns(Option<FeeSchedule>)]
+    GetFeeSchedule {
+        chain_id: String,
+        asset_id: Option<String>,
+    },
+
+    // Fees collected and not yet claimed
+    #[returns(Vec<Coin>)]
+    GetFeeBalances {},
+
+    #[returns(RateLimitResponse)]
+    GetRateLimit {
+        chain_id: String,
+        asset_id: Option<String>,
+    },
+
+    #[returns(UserQuotaResponse)]
+    GetUserQuota {
+        address: String,
+        asset_id: Option<String>,
+    },
+
+    #[returns(bool)]
+    IsSenderDenied { address: String },
+
+    #[returns(DestinationStatusResponse)]
+    GetDestinationStatus { chain_id: String, address: String },
+
+    // What a Bridge send of `amount` would charge and deliver. Given a sender and a
+    // destination, the rejection also covers their quota, address and compliance checks.
+    #[returns(QuoteResponse)]
+    QuoteSend {
+        chain_id: String,
+        asset_id: Option<String>,
+        amount: Uint128,
+        sender: Option<String>,
+        to: Option<String>,
+    },
+
+    #[returns(bool)]
+    IsNonceUsed {
+        chain_id: String,
+        asset_id: Option<String>,
+        nonce: Uint128,
+    },
+
+    #[returns(Transfer)]
+    GetTransfer { id: u64 },
+
+    #[returns(Vec<Transfer>)]
+    GetTransfers {
+        filter: Option<TransferFilter>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    // Remote connector functions driven by the typed execute messages
+    #[returns(Vec<RemoteFunctionInfo>)]
+    GetRemoteFunctions {},
+
+    #[returns(Option<JobResult>)]
+    GetJobResult { job_id: String },
+
+    #[returns(Vec<WithdrawAction>)]
+    GetWithdrawActions {
+        nonce: u64,
+        asset_id: Option<String>,
+    },
+}
+
+#[cw_serde]
+pub enum TransferFilter {
+    ChainId(String),
+    Destination(String),
+    Status(TransferStatus),
 }
 
 #[cw_serde]
@@ -128,8 +491,106 @@ pub struct CancelTx {
 
 #[cw_serde]
 pub struct ChainSettingInfo {
+    pub asset_id: String,
     pub chain_id: String,
     pub job_id: String,
+    pub chain_kind: ChainKind,
+    pub enabled: bool,
+    pub remote: RemoteConfig,
+}
+
+impl ChainSettingInfo {
+    pub fn new(asset_id: String, chain_id: String, chain_setting: ChainSetting) -> Self {
+        ChainSettingInfo {
+            asset_id,
+            chain_id,
+            job_id: chain_setting.job_id,
+            chain_kind: chain_setting.chain_kind,
+            enabled: chain_setting.enabled,
+            remote: chain_setting.remote,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct AssetInfo {
+    pub asset_id: String,
+    pub denom: String,
+    pub manager: Addr,
+    pub decimals: u8,
+}
+
+impl AssetInfo {
+    pub fn new(asset_id: String, asset: Asset) -> Self {
+        AssetInfo {
+            asset_id,
+            denom: asset.denom,
+            manager: asset.manager,
+            decimals: asset.decimals,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RateLimitResponse {
+    pub rate_limit: Option<RateLimit>,
+    // Usage over the rolling window ending at the current block
+    pub used: Uint128,
+    pub remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct UserQuotaResponse {
+    // Override of the address if set, the default quota otherwise
+    pub quota: Option<Uint128>,
+    // Days since the Unix epoch of the current block
+    pub day: u64,
+    pub used: Uint128,
+    pub remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct DestinationStatusResponse {
+    pub denied: bool,
+    pub allowlist_mode: bool,
+    pub allowlisted: bool,
+    // Whether transfers to the destination are currently accepted
+    pub allowed: bool,
+}
+
+#[cw_serde]
+pub struct QuoteResponse {
+    pub asset_id: String,
+    pub denom: String,
+    pub gross_amount: Uint128,
+    // Charged by this contract out of the gross amount
+    pub service_fee: Uint128,
+    pub net_amount: Uint128,
+    // Last fees pushed to the remote connector, charged there in its own units
+    pub remote_gas_fee: Option<Uint256>,
+    pub remote_service_fee: Option<Uint256>,
+    // Why the send would currently be rejected, if it would be
+    pub rejection: Option<String>,
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Addr,
+    pub pending_owner: Option<Addr>,
+    pub pending_expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: bool,
+    pub paused_chains: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RemoteFunctionInfo {
+    pub name: String,
+    pub signature: String,
+    pub selector: HexBinary,
 }
 
 impl CustomMsg for PalomaMsg {}
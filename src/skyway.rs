@@ -0,0 +1,188 @@
+use cosmwasm_std::{Coin, CosmosMsg, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{CancelTx, PalomaMsg, SendTx};
+
+// Skyway carries bridged amounts with 18 decimals on the remote chain
+pub const BRIDGE_DECIMALS: u8 = 18;
+
+pub fn validate_decimals(decimals: u8) -> Result<(), ContractError> {
+    if decimals > BRIDGE_DECIMALS {
+        return Err(ContractError::InvalidDecimals { decimals });
+    }
+    Ok(())
+}
+
+// Amount as seen by the remote chain, once scaled up to the bridge decimals
+pub fn bridge_amount(amount: Uint128, decimals: u8) -> Result<Uint128, ContractError> {
+    validate_decimals(decimals)?;
+    amount
+        .checked_mul(Uint128::new(10).pow(u32::from(BRIDGE_DECIMALS - decimals)))
+        .map_err(|_| ContractError::AmountOutOfRange { amount, decimals })
+}
+
+// Reject amounts the bridge cannot carry
+pub fn validate_amount(amount: Uint128, decimals: u8) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    bridge_amount(amount, decimals)?;
+    Ok(())
+}
+
+// Cosmos SDK denom format: [a-zA-Z][a-zA-Z0-9/:._-]{2,127}
+pub fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Skyway expects the Cosmos SDK coin string, e.g. `1000factory/paloma1.../upusd`
+pub fn wire_amount(coin: &Coin) -> String {
+    coin.amount.to_string() + coin.denom.as_str()
+}
+
+// Build the Skyway send_tx of `coin` to `destination`, checked against what the bridge can carry
+pub fn send_tx(
+    destination: &str,
+    coin: &Coin,
+    decimals: u8,
+    chain_id: &str,
+) -> Result<SendTx, ContractError> {
+    validate_amount(coin.amount, decimals)?;
+    validate_denom(&coin.denom)?;
+    Ok(SendTx {
+        remote_chain_destination_address: destination.to_string(),
+        amount: wire_amount(coin),
+        chain_reference_id: chain_id.to_string(),
+    })
+}
+
+pub fn send_tx_msg(send_tx: SendTx) -> CosmosMsg<PalomaMsg> {
+    CosmosMsg::Custom(PalomaMsg::SkywayMsg {
+        send_tx: Some(send_tx),
+        cancel_tx: None,
+    })
+}
+
+pub fn cancel_tx_msg(transaction_id: u64) -> CosmosMsg<PalomaMsg> {
+    CosmosMsg::Custom(PalomaMsg::SkywayMsg {
+        send_tx: None,
+        cancel_tx: Some(CancelTx { transaction_id }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DENOM: &str = "factory/paloma1manager/upusd";
+
+    #[test]
+    fn bridge_amount_scales_to_bridge_decimals() {
+        assert_eq!(
+            bridge_amount(Uint128::new(1_500_000), 6).unwrap(),
+            Uint128::new(1_500_000_000_000_000_000)
+        );
+        assert_eq!(bridge_amount(Uint128::new(7), 18).unwrap(), Uint128::new(7));
+        assert_eq!(bridge_amount(Uint128::zero(), 6).unwrap(), Uint128::zero());
+    }
+
+    #[test]
+    fn bridge_amount_rejects_overflow() {
+        assert!(matches!(
+            bridge_amount(Uint128::MAX, 6),
+            Err(ContractError::AmountOutOfRange { decimals: 6, .. })
+        ));
+        assert_eq!(bridge_amount(Uint128::MAX, 18).unwrap(), Uint128::MAX);
+    }
+
+    #[test]
+    fn bridge_amount_rejects_decimals_above_bridge() {
+        assert!(matches!(
+            bridge_amount(Uint128::one(), 19),
+            Err(ContractError::InvalidDecimals { decimals: 19 })
+        ));
+    }
+
+    #[test]
+    fn validate_amount_checks_zero_range_and_decimals() {
+        assert!(validate_amount(Uint128::one(), 6).is_ok());
+        assert!(matches!(
+            validate_amount(Uint128::zero(), 6),
+            Err(ContractError::ZeroAmount {})
+        ));
+        assert!(matches!(
+            validate_amount(Uint128::MAX, 0),
+            Err(ContractError::AmountOutOfRange { .. })
+        ));
+        assert!(matches!(
+            validate_amount(Uint128::one(), 24),
+            Err(ContractError::InvalidDecimals { decimals: 24 })
+        ));
+    }
+
+    #[test]
+    fn wire_amount_is_a_coin_string() {
+        assert_eq!(
+            wire_amount(&Coin::new(1000u128, DENOM)),
+            "1000factory/paloma1manager/upusd"
+        );
+        assert_eq!(wire_amount(&Coin::new(0u128, "upusd")), "0upusd");
+    }
+
+    #[test]
+    fn send_tx_builds_the_skyway_message() {
+        let send_tx = send_tx("0xabc", &Coin::new(25u128, DENOM), 6, "eth-main").unwrap();
+        assert_eq!(
+            send_tx,
+            SendTx {
+                remote_chain_destination_address: "0xabc".to_string(),
+                amount: "25factory/paloma1manager/upusd".to_string(),
+                chain_reference_id: "eth-main".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn send_tx_rejects_what_the_bridge_cannot_carry() {
+        assert!(matches!(
+            send_tx("0xabc", &Coin::new(0u128, DENOM), 6, "eth-main"),
+            Err(ContractError::ZeroAmount {})
+        ));
+        assert!(matches!(
+            send_tx("0xabc", &Coin::new(u128::MAX, DENOM), 6, "eth-main"),
+            Err(ContractError::AmountOutOfRange { .. })
+        ));
+        assert!(matches!(
+            send_tx("0xabc", &Coin::new(1u128, DENOM), 19, "eth-main"),
+            Err(ContractError::InvalidDecimals { decimals: 19 })
+        ));
+        assert!(matches!(
+            send_tx("0xabc", &Coin::new(1u128, "1bad"), 6, "eth-main"),
+            Err(ContractError::InvalidDenom { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_denom_follows_the_sdk_format() {
+        assert!(validate_denom("upusd").is_ok());
+        assert!(validate_denom(DENOM).is_ok());
+        assert!(validate_denom(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        )
+        .is_ok());
+        assert!(validate_denom("ab").is_err());
+        assert!(validate_denom("1pusd").is_err());
+        assert!(validate_denom("u pusd").is_err());
+        assert!(validate_denom(&"u".repeat(129)).is_err());
+    }
+}
@@ -1,19 +1,535 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Timestamp, Uint128, Uint256};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Key, KeyDeserialize, Map, MultiIndex, Prefixer, PrimaryKey,
+};
+
+use crate::address;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct State {
+    pub owner: Addr,
+    // Asset used when a message does not name one
+    pub default_asset: String,
+}
+
+// State written before assets were registered, converted on migrate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LegacyState {
     pub owner: Addr,
     pub pusd_manager: Addr,
+    #[serde(default)]
+    pub pusd_denom: String,
+}
+
+// Token bridged by the connector, with the manager contract handling its withdrawals
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Asset {
+    pub denom: String,
+    pub manager: Addr,
+    pub decimals: u8,
+}
+
+// Tokenfactory denom minted by a pusd manager
+pub fn default_pusd_denom(pusd_manager: &Addr) -> String {
+    "factory/".to_string() + pusd_manager.as_str() + "/upusd"
+}
+
+// Owner proposed through ProposeOwner, waiting for AcceptOwnership
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expires_at: Option<Timestamp>,
+}
+
+// Roles that can be granted to addresses. The owner implicitly holds every role.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Operator,
+    FeeManager,
+    Guardian,
+    FeeCollector,
+    Compliance,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Operator => "operator",
+            Role::FeeManager => "fee_manager",
+            Role::Guardian => "guardian",
+            Role::FeeCollector => "fee_collector",
+            Role::Compliance => "compliance",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> StdResult<Self> {
+        match s {
+            "admin" => Ok(Role::Admin),
+            "operator" => Ok(Role::Operator),
+            "fee_manager" => Ok(Role::FeeManager),
+            "guardian" => Ok(Role::Guardian),
+            "fee_collector" => Ok(Role::FeeCollector),
+            "compliance" => Ok(Role::Compliance),
+            _ => Err(StdError::parse_err("Role", format!("unknown role {s}"))),
+        }
+    }
+}
+
+impl<'a> PrimaryKey<'a> for Role {
+    type Prefix = ();
+    type SubPrefix = ();
+    type Suffix = Self;
+    type SuperSuffix = Self;
+
+    fn key(&self) -> Vec<Key<'_>> {
+        vec![Key::Ref(self.as_str().as_bytes())]
+    }
+}
+
+impl<'a> Prefixer<'a> for Role {
+    fn prefix(&self) -> Vec<Key<'_>> {
+        vec![Key::Ref(self.as_str().as_bytes())]
+    }
+}
+
+impl KeyDeserialize for Role {
+    type Output = Role;
+
+    const KEY_ELEMS: u16 = 1;
+
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        String::from_utf8(value)
+            .map_err(StdError::invalid_utf8)?
+            .parse()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct ChainSetting {
     pub job_id: String,
+    // Address format of the chain, used to validate destinations and remote config
+    #[serde(default)]
+    pub chain_kind: ChainKind,
+    // Disabled chains reject every outbound flow until re-enabled
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Last configuration pushed to the remote connector
+    #[serde(default)]
+    pub remote: RemoteConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainKind {
+    #[default]
+    Evm,
+    Tron,
+    Solana,
+    // Bech32 addresses with this human-readable prefix
+    Cosmos {
+        prefix: String,
+    },
+}
+
+impl ChainKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChainKind::Evm => "evm",
+            ChainKind::Tron => "tron",
+            ChainKind::Solana => "solana",
+            ChainKind::Cosmos { .. } => "cosmos",
+        }
+    }
+}
+
+// Value pushed to the remote connector, with the block it was pushed at
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Recorded<T> {
+    pub value: T,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+impl<T> Recorded<T> {
+    pub fn new(value: T, block: &BlockInfo) -> Self {
+        Recorded {
+            value,
+            height: block.height,
+            time: block.time,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct RemoteConfig {
+    pub pusd: Option<Recorded<String>>,
+    pub pusd_manager: Option<Recorded<String>>,
+    pub refund_wallet: Option<Recorded<String>>,
+    pub gas_fee: Option<Recorded<Uint256>>,
+    pub service_fee: Option<Recorded<Uint256>>,
+    pub service_fee_collector: Option<Recorded<String>>,
+    pub withdraw_limit: Option<Recorded<Uint256>>,
+    pub paloma_set: Option<Recorded<bool>>,
+}
+
+// Outbound transfer issued through Skyway
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Transfer {
+    pub id: u64,
+    pub origin: TransferOrigin,
+    pub asset_id: String,
+    pub chain_id: String,
+    pub destination: String,
+    pub amount: Uint128,
+    pub denom: String,
+    // Charged on top of `amount` and kept in the fee balance
+    pub fee: Uint128,
+    // Set for operator transfers sent through SendPusd
+    pub nonce: Option<Uint128>,
+    pub sender: Addr,
+    pub created_at: Timestamp,
+    pub status: TransferStatus,
+    pub updated_at: Timestamp,
+    // Skyway outgoing transaction id, learned from the send_tx reply
+    pub skyway_tx_id: Option<u64>,
+    // Amount paid back to the sender of a Bridge transfer that was cancelled or refunded
+    #[serde(default)]
+    pub refunded: Option<Uint128>,
+}
+
+// Who funded a transfer
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferOrigin {
+    // Sent by an operator through SendPusd out of the contract's own balance
+    Operator,
+    // Paid for by the sender through Bridge
+    Bridge,
+}
+
+impl TransferOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferOrigin::Operator => "operator",
+            TransferOrigin::Bridge => "bridge",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Pending,
+    Cancelled,
+    Confirmed,
+    Refunded,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "pending",
+            TransferStatus::Cancelled => "cancelled",
+            TransferStatus::Confirmed => "confirmed",
+            TransferStatus::Refunded => "refunded",
+        }
+    }
+
+    pub fn can_transition_to(&self, next: TransferStatus) -> bool {
+        match self {
+            TransferStatus::Pending => next != TransferStatus::Pending,
+            // Skyway only cancels a transaction that is not batched yet, and fails the whole
+            // CancelTx otherwise, so a cancelled transfer can no longer be relayed or refunded
+            TransferStatus::Cancelled | TransferStatus::Confirmed | TransferStatus::Refunded => {
+                false
+            }
+        }
+    }
+}
+
+// Retry or cancellation of an asset manager withdrawal, and who issued it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct WithdrawAction {
+    pub kind: WithdrawActionKind,
+    pub sender: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawActionKind {
+    ReWithdraw,
+    CancelWithdraw,
+}
+
+// Fee charged on user-funded sends: flat + bps of the amount, clamped to [min, max]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeSchedule {
+    pub flat: Uint128,
+    pub bps: u16,
+    pub min: Uint128,
+    pub max: Option<Uint128>,
+}
+
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+impl FeeSchedule {
+    pub fn fee(&self, amount: Uint128) -> StdResult<Uint128> {
+        let fee = self
+            .flat
+            .checked_add(amount.multiply_ratio(self.bps, MAX_FEE_BPS))?
+            .max(self.min);
+        Ok(match self.max {
+            Some(max) => fee.min(max),
+            None => fee,
+        })
+    }
+}
+
+// Outbound cap of a chain: max_amount per rolling window of window_seconds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RateLimit {
+    pub window_seconds: u64,
+    pub max_amount: Uint128,
+    pub max_per_transfer: Option<Uint128>,
+}
+
+// Amount sent in the current fixed window and the one before it. The rolling usage weighs
+// the previous window by the share of it still inside the rolling window.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct RateLimitUsage {
+    pub window_start: u64,
+    pub current: Uint128,
+    pub previous: Uint128,
+}
+
+impl RateLimitUsage {
+    // Move the fixed windows forward to the one holding `now`
+    pub fn roll(&mut self, now: u64, window_seconds: u64) {
+        let window_start = now - now % window_seconds;
+        if window_start == self.window_start {
+            return;
+        }
+        // A start off the new alignment means the window length changed. How much of the
+        // recorded usage still falls in the rolling window is unknown, so all of it is kept
+        // in the current window until the new window has fully passed.
+        if !self.window_start.is_multiple_of(window_seconds) {
+            self.current = if now - self.window_start < window_seconds {
+                self.current.saturating_add(self.previous)
+            } else {
+                Uint128::zero()
+            };
+            self.previous = Uint128::zero();
+            self.window_start = window_start;
+            return;
+        }
+        self.previous = if window_start == self.window_start + window_seconds {
+            self.current
+        } else {
+            Uint128::zero()
+        };
+        self.current = Uint128::zero();
+        self.window_start = window_start;
+    }
+
+    // Usage over the rolling window ending at `now`, once rolled to it
+    pub fn used(&self, now: u64, window_seconds: u64) -> Uint128 {
+        let remaining_share = window_seconds - (now - self.window_start);
+        self.previous
+            .multiply_ratio(remaining_share, window_seconds)
+            .saturating_add(self.current)
+    }
+}
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// Amount a sender bridged on a day, counted in days since the Unix epoch
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct UserUsage {
+    pub day: u64,
+    pub used: Uint128,
+}
+
+// Outcome of the last run of a Scheduler job, reported by Paloma
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct JobResult {
+    pub chain_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+pub struct TransferIndexes<'a> {
+    pub chain_id: MultiIndex<'a, String, Transfer, u64>,
+    pub destination: MultiIndex<'a, String, Transfer, u64>,
+    pub status: MultiIndex<'a, String, Transfer, u64>,
+    pub asset_status: MultiIndex<'a, (String, String), Transfer, u64>,
+}
+
+impl IndexList<Transfer> for TransferIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Transfer>> + '_> {
+        let v: Vec<&dyn Index<Transfer>> = vec![
+            &self.chain_id,
+            &self.destination,
+            &self.status,
+            &self.asset_status,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn transfers<'a>() -> IndexedMap<u64, Transfer, TransferIndexes<'a>> {
+    let indexes = TransferIndexes {
+        chain_id: MultiIndex::new(
+            |_pk, transfer| transfer.chain_id.clone(),
+            "transfers",
+            "transfers__chain_id",
+        ),
+        // Canonical form, so a lookup matches however the address was cased
+        destination: MultiIndex::new(
+            |_pk, transfer| address::canonical(&transfer.destination),
+            "transfers",
+            "transfers__destination",
+        ),
+        status: MultiIndex::new(
+            |_pk, transfer| transfer.status.as_str().to_string(),
+            "transfers",
+            "transfers__status",
+        ),
+        asset_status: MultiIndex::new(
+            |_pk, transfer| {
+                (
+                    transfer.asset_id.clone(),
+                    transfer.status.as_str().to_string(),
+                )
+            },
+            "transfers",
+            "transfers__asset_status",
+        ),
+    };
+    IndexedMap::new("transfers", indexes)
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 pub const STATE: Item<State> = Item::new("state");
-pub const CHAIN_SETTINGS: Map<String, ChainSetting> = Map::new("chain_settings");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const LEGACY_STATE: Item<LegacyState> = Item::new("state");
+pub const ASSETS: Map<String, Asset> = Map::new("assets");
+// Settings of each chain an asset is bridged to, keyed by (asset_id, chain_id)
+pub const CHAIN_SETTINGS: Map<(String, String), ChainSetting> = Map::new("asset_chain_settings");
+// Chain settings written before assets were registered, moved to the default asset on migrate
+pub const LEGACY_CHAIN_SETTINGS: Map<String, ChainSetting> = Map::new("chain_settings");
+pub const ROLES: Map<(Role, Addr), ()> = Map::new("roles");
+// Global pause flag and chains paused individually
+pub const PAUSED: Item<bool> = Item::new("paused");
+pub const PAUSED_CHAINS: Map<String, ()> = Map::new("paused_chains");
+// SendPusd nonces already consumed per asset and chain, with the transfer they created
+pub const USED_NONCES: Map<(String, String, u128), u64> = Map::new("used_nonces");
+pub const TRANSFER_COUNT: Item<u64> = Item::new("transfer_count");
+// Skyway transaction id to transfer id
+pub const SKYWAY_TXS: Map<u64, u64> = Map::new("skyway_txs");
+// Fee schedule of user-funded sends, keyed by (asset_id, chain_id)
+pub const FEE_SCHEDULES: Map<(String, String), FeeSchedule> = Map::new("fee_schedules");
+// Outbound rate limits and their usage, keyed by (asset_id, chain_id)
+pub const RATE_LIMITS: Map<(String, String), RateLimit> = Map::new("rate_limits");
+pub const RATE_LIMIT_USAGE: Map<(String, String), RateLimitUsage> = Map::new("rate_limit_usage");
+// Daily quota of every sender of an asset, and per-sender overrides
+pub const DEFAULT_USER_QUOTAS: Map<String, Uint128> = Map::new("default_user_quotas");
+pub const USER_QUOTAS: Map<(String, Addr), Uint128> = Map::new("user_quotas");
+pub const USER_USAGE: Map<(String, Addr), UserUsage> = Map::new("user_usage");
+// Cosmos senders refused by every outbound flow
+pub const DENIED_SENDERS: Map<Addr, ()> = Map::new("denied_senders");
+// Remote destinations keyed by (chain_id, canonical address)
+pub const DENIED_DESTINATIONS: Map<(String, String), ()> = Map::new("denied_destinations");
+pub const ALLOWED_DESTINATIONS: Map<(String, String), ()> = Map::new("allowed_destinations");
+// Chains that only accept allowlisted destinations
+pub const ALLOWLIST_CHAINS: Map<String, ()> = Map::new("allowlist_chains");
+// Fees collected and not yet claimed, per denom
+pub const FEE_BALANCES: Map<String, Uint128> = Map::new("fee_balances");
+pub const JOB_RESULTS: Map<String, JobResult> = Map::new("job_results");
+// Actions taken on asset manager withdrawals, keyed by (asset_id, withdrawal nonce)
+pub const WITHDRAW_ACTIONS: Map<(String, u64), Vec<WithdrawAction>> = Map::new("withdraw_actions");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(window_start: u64, current: u128, previous: u128) -> RateLimitUsage {
+        RateLimitUsage {
+            window_start,
+            current: Uint128::new(current),
+            previous: Uint128::new(previous),
+        }
+    }
+
+    #[test]
+    fn roll_within_the_same_window_keeps_usage() {
+        let mut state = usage(1000, 40, 60);
+        state.roll(1099, 100);
+        assert_eq!(state, usage(1000, 40, 60));
+        // 1 second left of the previous window's weight
+        assert_eq!(state.used(1099, 100), Uint128::new(40));
+        assert_eq!(state.used(1050, 100), Uint128::new(70));
+    }
+
+    #[test]
+    fn roll_to_the_adjacent_window_carries_current() {
+        let mut state = usage(1000, 40, 60);
+        state.roll(1125, 100);
+        assert_eq!(state, usage(1100, 0, 40));
+        assert_eq!(state.used(1125, 100), Uint128::new(30));
+    }
+
+    #[test]
+    fn roll_past_a_skipped_window_drops_usage() {
+        let mut state = usage(1000, 40, 60);
+        state.roll(1200, 100);
+        assert_eq!(state, usage(1200, 0, 0));
+        assert_eq!(state.used(1200, 100), Uint128::zero());
+    }
+
+    #[test]
+    fn roll_after_a_window_change_realigns() {
+        // Same start under the new window: usage is kept
+        let mut state = usage(1200, 40, 60);
+        state.roll(1250, 300);
+        assert_eq!(state, usage(1200, 40, 60));
+        assert_eq!(state.used(1250, 300), Uint128::new(90));
+        // Start off the new alignment: all usage moves to the current window
+        let mut state = usage(1000, 40, 60);
+        state.roll(1050, 60);
+        assert_eq!(state, usage(1020, 100, 0));
+        assert_eq!(state.used(1050, 60), Uint128::new(100));
+        // and is dropped once a whole new window has passed since the old start
+        let mut state = usage(1000, 40, 60);
+        state.roll(1060, 60);
+        assert_eq!(state, usage(1020, 0, 0));
+        assert_eq!(state.used(1060, 60), Uint128::zero());
+        // Adjacent under the new window: current becomes previous
+        let mut state = usage(1000, 40, 60);
+        state.roll(1550, 500);
+        assert_eq!(state, usage(1500, 0, 40));
+        assert_eq!(state.used(1550, 500), Uint128::new(36));
+    }
+}